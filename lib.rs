@@ -5,13 +5,81 @@ mod tests;
 #[ink::contract]
 mod betting {
     use ink::storage::Mapping;
+    use ink::storage::traits::StorageKey;
 
-    // Use BoundedVec?
-    pub type TeamName = Vec<u8>;
+    /// Longest name of a team, in bytes.
+    pub const MAX_TEAM_NAME_LEN: usize = 64;
 
+    /// Name of a team, an outcome or a competition: valid UTF-8 of 1 to `MAX_TEAM_NAME_LEN` bytes.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TeamName(Vec<u8>);
+
+    impl TeamName {
+        /// Checks the name is not empty, not too long and valid UTF-8.
+        pub fn validate(&self) -> Result<(), Error> {
+            if self.0.is_empty() {
+                return Err(Error::EmptyTeamName);
+            }
+            if self.0.len() > MAX_TEAM_NAME_LEN {
+                return Err(Error::TeamNameTooLong);
+            }
+            if core::str::from_utf8(&self.0).is_err() {
+                return Err(Error::InvalidTeamName);
+            }
+            Ok(())
+        }
+
+        pub fn as_bytes(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl TryFrom<Vec<u8>> for TeamName {
+        type Error = Error;
+
+        fn try_from(name: Vec<u8>) -> Result<Self, Self::Error> {
+            let name = Self(name);
+            name.validate()?;
+            Ok(name)
+        }
+    }
+
+    /// Identifier of a match, assigned incrementally on creation.
+    pub type MatchId = u32;
+    /// Identifier of a bet inside its match, assigned incrementally.
+    pub type BetId = u32;
+    /// Bets placed by an account in a match.
+    pub type BetIds = Vec<BetId>;
+    /// A bet identified by its match.
+    pub type BetKey = (MatchId, BetId);
+
+    /// Minimum deposit to create a match until an admin changes it.
     const MIN_DEPOSIT: Balance = 1_000_000_000_000;
+    /// Smallest stake accepted by any match until an admin changes it, lower stakes are dust.
+    const MIN_STAKE: Balance = 1_000_000_000;
+    /// Version of the layout of the matches and bets in the storage.
+    const STORAGE_VERSION: u32 = 2;
+    /// Highest protocol fee, in basis points (10%).
+    pub const MAX_FEE_BPS: u16 = 1_000;
+    /// Highest commission of the creator of a match, in basis points (10%).
+    pub const MAX_COMMISSION_BPS: u16 = 1_000;
+    const BPS_DENOMINATOR: Balance = 10_000;
+    /// Most matches returned by a paginated query.
+    pub const MAX_PAGE_SIZE: u32 = 100;
+    /// Highest number of outcomes of a market.
+    pub const MAX_OUTCOMES: u32 = 32;
+    /// Version of the layout of `MatchView`, increased on every change to it.
+    pub const MATCH_VIEW_VERSION: u16 = 1;
+
+    /// Index of an outcome of the market of a match.
+    pub type Outcome = u32;
 
-    #[derive(scale::Decode, scale::Encode, PartialEq, Clone, Copy)]
+    /// Outcomes of the `Market::MatchResult` market.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
@@ -21,7 +89,117 @@ mod betting {
         Team2Victory,
         Draw,
     }
-    #[derive(scale::Decode, scale::Encode, PartialEq)]
+
+    impl From<MatchResult> for Outcome {
+        fn from(result: MatchResult) -> Self {
+            result as Outcome
+        }
+    }
+
+    /// What the bets of a match are placed on, it defines the outcomes of the match.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Market {
+        /// Victory of team 1, victory of team 2 or draw, indexed as `MatchResult`.
+        #[default]
+        MatchResult,
+        /// One of the named outcomes wins, e.g. the winner of a tournament or a race.
+        Outcomes(Vec<TeamName>),
+        /// The total score is over (outcome 0) or under (outcome 1) the line, in tenths of a point.
+        /// The line is a half point (ends in 5) so the score can not land on it.
+        OverUnder { line: u32 },
+        /// Team 1 wins with the line, in tenths of a point, added to its score (outcome 0)
+        /// or team 2 does (outcome 1). The line is a half point, as in `OverUnder`.
+        Handicap { line: i32 },
+    }
+
+    impl Market {
+        /// Number of outcomes of the market.
+        pub fn outcomes(&self) -> u32 {
+            match self {
+                Market::MatchResult => 3,
+                Market::Outcomes(names) => names.len() as u32,
+                Market::OverUnder { .. } | Market::Handicap { .. } => 2,
+            }
+        }
+
+        /// Whether the line of the market can end in a push, a score equal to the line.
+        fn has_push(&self) -> bool {
+            match self {
+                Market::OverUnder { line } => line % 10 != 5,
+                Market::Handicap { line } => line.unsigned_abs() % 10 != 5,
+                _ => false,
+            }
+        }
+    }
+
+    /// Stage of the lifecycle of a match.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum MatchStatus {
+        /// Accepting bets or waiting for its result.
+        Open,
+        /// The reporters of the quorum could not agree on the result or a bettor challenged it,
+        /// an admin has to resolve it.
+        Disputed,
+        /// The winnings have been distributed.
+        Settled,
+        /// The match has been voided and the stakes are refunded.
+        Cancelled,
+    }
+
+    /// Why a match has been cancelled.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum CancelReason {
+        /// The match will not be played on the scheduled time.
+        Postponed,
+        /// The match was started but could not be finished.
+        Abandoned,
+        /// The match should never have been created, the deposit of the creator is slashed.
+        Invalid,
+    }
+
+    /// Permissions that the owner and the admins can grant to other accounts.
+    /// The owner of the Smart Contract implicitly holds every role.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Role {
+        /// Manages the reporters and the settings of the Smart Contract.
+        Admin,
+        /// Sets the result of any match.
+        Reporter,
+        /// Sets the result of a single match.
+        MatchReporter(MatchId),
+    }
+
+    /// What happens with the pot of a match when nobody bet on its result.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum NoWinnersPolicy {
+        /// Every bettor gets its stake back.
+        #[default]
+        Refund,
+        /// The pot goes to the treasury of the Smart Contract.
+        House,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
@@ -31,8 +209,12 @@ mod betting {
         pub bettor: AccountId,
         /// Bet amount.
         pub amount: Balance,
-        /// Result predicted.
-        pub result: MatchResult,
+        /// Outcome predicted.
+        pub result: Outcome,
+        /// Odds locked when the bet was placed, in basis points, if the match has fixed odds.
+        pub odds: Option<u32>,
+        /// Whether the winnings of the bet have been claimed.
+        pub claimed: bool,
     }
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
@@ -40,59 +222,567 @@ mod betting {
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     pub struct Match {
+        /// Account that created the match.
+        pub creator: AccountId,
         /// Starting block of the match.
-        start: BlockNumber,
+        pub start: BlockNumber,
         /// Length of the match (start + length = end).
-        length: BlockNumber,
+        pub length: BlockNumber,
         /// Team1 name.
-        team1: TeamName,
+        pub team1: TeamName,
         /// Team2 name.
-        team2: TeamName,
-        /// Result.
-        result: Option<MatchResult>,
-        /// List of bets.
-        pub bets: Vec<Bet>,
+        pub team2: TeamName,
+        /// Winning outcome.
+        pub result: Option<Outcome>,
+        /// What the bets are placed on.
+        pub market: Market,
+        /// Share of the pot earned by the creator on settlement, in basis points.
+        pub commission_bps: u16,
+        /// Bets accepted by the match.
+        pub bet_limits: BetLimits,
+        /// Odds offered on each result, in basis points, if the match has fixed odds.
+        pub odds: Option<Vec<u32>>,
+        /// Payout owed to the winners of each result at their locked odds.
+        pub payouts: Vec<Balance>,
+        /// Block until which the result can be challenged.
+        pub challenge_end: BlockNumber,
+        /// Number of bets placed, also the id of the next bet.
+        pub bets_count: BetId,
+        /// Total amount bet on each outcome.
+        pub totals: Vec<Balance>,
         /// The amount held in reserve of the `depositor`,
         /// To be returned once this recovery process is closed.
-        deposit: Balance,
+        pub deposit: Balance,
+        /// Stage of the lifecycle of the match.
+        pub status: MatchStatus,
+        /// Whether an admin stopped the changes to the match.
+        pub frozen: bool,
+        /// Amount to be shared between the winners, recorded on settlement.
+        pub pot: Balance,
+        /// Whether the bettors get their stakes back instead of winnings.
+        pub refunds: bool,
+        /// Winning stakes whose winnings have been claimed.
+        claimed_stake: Balance,
+        /// Winnings paid out to the winners.
+        paid_out: Balance,
+    }
+
+    /// Reporters assigned to a match, its result is final when `threshold` of them agree.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Quorum {
+        /// Accounts allowed to submit the result.
+        pub reporters: Vec<AccountId>,
+        /// Number of reporters that must agree on the result.
+        pub threshold: u32,
+        /// Number of reporters that submitted a result.
+        pub submitted: u32,
+        /// Number of reporters that submitted each outcome.
+        pub votes: Vec<u32>,
+    }
+
+    /// Phase of a match, from its status and the current block.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum MatchPhase {
+        /// Accepting bets.
+        Open,
+        /// Started and not over yet.
+        InPlay,
+        /// Over and not settled yet: waiting for its result, the end of the challenge period
+        /// or the resolution of a dispute.
+        AwaitingResult,
+        Settled,
+        Cancelled,
+    }
+
+    /// State of a bet for its bettor.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum PositionStatus {
+        /// The match is not settled yet.
+        Pending,
+        /// The bet won, its winnings can be claimed.
+        Won,
+        /// The stake can be claimed back, the match was cancelled or nobody won.
+        Refundable,
+        /// The bet did not win.
+        Lost,
+        /// The winnings or the stake have been claimed.
+        Claimed,
+    }
+
+    /// Summary of a match returned by `match_view`, stable across changes to the storage layout.
+    /// `version` is `MATCH_VIEW_VERSION` of the code that built it.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct MatchView {
+        pub version: u16,
+        pub match_id: MatchId,
+        pub creator: AccountId,
+        pub team1: TeamName,
+        pub team2: TeamName,
+        pub market: Market,
+        /// Share of the pot earned by the creator on settlement, in basis points.
+        pub commission_bps: u16,
+        /// Bets accepted by the match.
+        pub bet_limits: BetLimits,
+        /// Block when the match starts and the bets are closed.
+        pub start: BlockNumber,
+        /// Block when the match ends.
+        pub end: BlockNumber,
+        /// Block until which the result can be challenged.
+        pub challenge_end: BlockNumber,
+        pub status: MatchStatus,
+        pub phase: MatchPhase,
+        /// Winning outcome, once set.
+        pub result: Option<Outcome>,
+        /// Total amount bet on each outcome.
+        pub totals: Vec<Balance>,
+        /// Number of bets placed, including the cancelled ones.
+        pub bets_count: BetId,
+        /// Odds offered on each outcome, if the match has fixed odds.
+        pub odds: Option<Vec<u32>>,
+        pub deposit: Balance,
+        /// Amount shared between the winners, recorded on settlement.
+        pub pot: Balance,
+    }
+
+    /// A bet of an account, as returned by `positions_of`.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Position {
+        pub match_id: MatchId,
+        pub bet_id: BetId,
+        /// Stake of the bet.
+        pub amount: Balance,
+        /// Outcome predicted.
+        pub result: Outcome,
+        pub status: PositionStatus,
+        /// Amount the bettor gets when it claims the bet.
+        pub claimable: Balance,
+    }
+
+    /// Indexes of the matches by their status.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    enum MatchIndex {
+        /// Open or disputed matches.
+        Active,
+        Settled,
+        Cancelled,
+    }
+
+    /// Sport played in a match.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Sport {
+        Football,
+        Basketball,
+        Tennis,
+        AmericanFootball,
+        Baseball,
+        IceHockey,
+        Cricket,
+        Rugby,
+        Esports,
+        Racing,
+        Other,
+    }
+
+    /// Optional information to categorise a match and verify it off-chain.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct MatchMetadata {
+        pub sport: Option<Sport>,
+        /// League or tournament, with the same rules as a team name.
+        pub competition: Option<TeamName>,
+        /// Year the season starts.
+        pub season: Option<u16>,
+        /// Id of the fixture in an external data provider. It is part of the hash of the match, so
+        /// several matches (e.g. markets) can share it but not with the same teams, times and market.
+        pub fixture_id: Option<u64>,
+        /// Hash of the off-chain metadata, e.g. the digest of an IPFS document.
+        pub metadata_hash: Option<Hash>,
+    }
+
+    /// Bets accepted by a match.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct BetLimits {
+        /// Smallest stake of a bet.
+        pub min_stake: Balance,
+        /// Largest stake of a bet.
+        pub max_stake: Balance,
+        /// Number of bets an account can place.
+        pub max_bets: u32,
+    }
+
+    impl Default for BetLimits {
+        fn default() -> Self {
+            Self {
+                min_stake: 0,
+                max_stake: Balance::MAX,
+                max_bets: 1,
+            }
+        }
+    }
+
+    /// A bettor disputing the result of a match.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Challenge {
+        /// Account of the bettor.
+        pub challenger: AccountId,
+        /// Bond returned if the result is changed, slashed otherwise.
+        pub bond: Balance,
+    }
+
+    /// Layout of a `Bet` in the storage version 1, before the markets and fixed odds.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct BetV1 {
+        pub bettor: AccountId,
+        pub amount: Balance,
+        pub result: MatchResult,
+        pub claimed: bool,
+    }
+
+    /// Layout of a `Match` in the storage version 1, before the markets, commissions, bet limits
+    /// and fixed odds.
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct MatchV1 {
+        pub creator: AccountId,
+        pub start: BlockNumber,
+        pub length: BlockNumber,
+        pub team1: Vec<u8>,
+        pub team2: Vec<u8>,
+        pub result: Option<MatchResult>,
+        pub challenge_end: BlockNumber,
+        pub bets_count: BetId,
+        pub totals: Vec<Balance>,
+        pub deposit: Balance,
+        pub status: MatchStatus,
+        pub frozen: bool,
+        pub pot: Balance,
+        pub refunds: bool,
+        pub claimed_stake: Balance,
+        pub paid_out: Balance,
     }
 
     #[ink(storage)]
     pub struct Betting {
-        /// Mapping of open matches.
-        matches: Mapping<AccountId, Match>,
-        // Mapping of all match hashes. (hash -> owner)
-        matches_hashes: Mapping<[u8;32], AccountId>,
-        /// Owner of the Smart Contract (sudo)
-        owner: AccountId,
+        /// Mapping of all matches.
+        matches: Mapping<MatchId, Match>,
+        // Mapping of all match hashes. (hash -> match id)
+        matches_hashes: Mapping<[u8;32], MatchId>,
+        /// Bets placed on each match.
+        bets: Mapping<(MatchId, BetId), Bet>,
+        /// Bet placed by each account on a match.
+        bettors: Mapping<(MatchId, AccountId), BetIds>,
+        /// Matches of each creator in creation order. (creator, position) -> match id
+        creator_matches: Mapping<(AccountId, u32), MatchId>,
+        /// Number of matches of each creator.
+        creator_matches_count: Mapping<AccountId, u32>,
+        /// Matches of each index. (index, position) -> match id
+        index: Mapping<(MatchIndex, u32), MatchId>,
+        /// Number of matches of each index.
+        index_len: Mapping<MatchIndex, u32>,
+        /// Position of each match in its index.
+        index_position: Mapping<MatchId, u32>,
+        /// Bets of each account. (account, position) -> (match id, bet id)
+        positions: Mapping<(AccountId, u32), BetKey>,
+        /// Number of bets of each account.
+        positions_count: Mapping<AccountId, u32>,
+        /// Position of each bet in the bets of its account.
+        position_of: Mapping<BetKey, u32>,
+        /// Metadata of the matches created with it.
+        metadata: Mapping<MatchId, MatchMetadata>,
+        /// Id that will be assigned to the next created match.
+        next_match_id: MatchId,
+        /// Owner of the Smart Contract (sudo), `None` once renounced.
+        owner: Option<AccountId>,
+        /// Account that can accept the ownership of the Smart Contract.
+        pending_owner: Option<AccountId>,
+        /// Roles granted to each account.
+        roles: Mapping<(AccountId, Role), ()>,
+        /// Quorum of reporters of the matches that require one.
+        quorums: Mapping<MatchId, Quorum>,
+        /// Result submitted by each reporter of a quorum.
+        submissions: Mapping<(MatchId, AccountId), Outcome>,
+        /// Challenge to the result of each disputed match.
+        challenges: Mapping<MatchId, Challenge>,
+        /// Whether an admin stopped the changes to all the matches.
+        paused: bool,
+        /// Blocks during which the result of a match can be challenged.
+        challenge_period: BlockNumber,
+        /// Minimum bond to challenge the result of a match.
+        dispute_bond: Balance,
+        /// What happens with the pot of the matches without winners.
+        no_winners_policy: NoWinnersPolicy,
+        /// Version of the layout of the matches and bets in the storage.
+        version: u32,
+        /// Next match to be converted by `migrate`.
+        migrated_until: MatchId,
+        /// Protocol fee taken from each settled pot, in basis points.
+        fee_bps: u16,
+        /// Fees and funds of the house collected and not withdrawn yet.
+        treasury: Balance,
+        /// Minimum deposit to create a match.
+        min_deposit: Balance,
+        /// Smallest stake accepted by any match.
+        min_stake: Balance,
+        /// Largest stake accepted by any match.
+        max_stake: Balance,
     }
 
-    /// A new match has been created. [who, team1, team2, start, length]
+    /// A new match has been created. [matchId, who, team1, team2, start, length, commission_bps, odds, metadata]
     #[ink(event)]
     pub struct MatchCreated {
+        #[ink(topic)]
+        match_id: MatchId,
         #[ink(topic)]
         who: AccountId,
         team1: TeamName,
         team2: TeamName,
         start: BlockNumber,
-        length: BlockNumber
+        length: BlockNumber,
+        commission_bps: u16,
+        odds: Option<Vec<u32>>,
+        metadata: MatchMetadata,
     }
-    /// A new bet has been created. [matchId, who, amount, result]
+    /// The creator of a fixed odds match published new odds. [matchId, odds]
+    #[ink(event)]
+    pub struct OddsUpdated {
+        #[ink(topic)]
+        match_id: MatchId,
+        odds: Vec<u32>,
+    }
+    /// A new bet has been created. [matchId, who, bet_id, amount, result]
     #[ink(event)]
     pub struct BetPlaced {
         #[ink(topic)]
-        match_id: AccountId,
+        match_id: MatchId,
+        #[ink(topic)]
+        who: AccountId,
+        bet_id: BetId,
+        amount: Balance,
+        result: Outcome,
+    }
+    /// A bettor added to the stake of a bet. [matchId, who, bet_id, amount, total]
+    #[ink(event)]
+    pub struct BetIncreased {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        who: AccountId,
+        bet_id: BetId,
+        amount: Balance,
+        total: Balance,
+    }
+    /// A bettor changed the result predicted by a bet. [matchId, who, bet_id, result]
+    #[ink(event)]
+    pub struct PredictionChanged {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        who: AccountId,
+        bet_id: BetId,
+        result: Outcome,
+    }
+    /// A bettor withdrew a bet and got its stake back. [matchId, who, bet_id, amount]
+    #[ink(event)]
+    pub struct BetCancelled {
+        #[ink(topic)]
+        match_id: MatchId,
         #[ink(topic)]
         who: AccountId,
+        bet_id: BetId,
         amount: Balance,
-        result: MatchResult,
     }
     /// A match result has been set. [matchId, result]
     #[ink(event)]
     pub struct MatchResultSet {
         #[ink(topic)]
-        match_id: AccountId, 
-        result: MatchResult,
+        match_id: MatchId,
+        result: Outcome,
+    }
+    /// A reporter of a quorum has submitted a result. [matchId, reporter, result]
+    #[ink(event)]
+    pub struct ResultSubmitted {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        reporter: AccountId,
+        result: Outcome,
+    }
+    /// The reporters of a quorum could not agree on the result. [matchId]
+    #[ink(event)]
+    pub struct MatchDisputed {
+        #[ink(topic)]
+        match_id: MatchId,
+    }
+    /// A bettor has challenged the result of a match. [matchId, challenger, bond]
+    #[ink(event)]
+    pub struct ResultChallenged {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        challenger: AccountId,
+        bond: Balance,
+    }
+    /// An admin has ruled on a challenge, the bond is returned if it was upheld. [matchId, challenger, upheld]
+    #[ink(event)]
+    pub struct ChallengeResolved {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        challenger: AccountId,
+        upheld: bool,
+    }
+    /// The winnings of a match have been distributed. [matchId, pot, winning_total, commission]
+    #[ink(event)]
+    pub struct MatchSettled {
+        #[ink(topic)]
+        match_id: MatchId,
+        pot: Balance,
+        winning_total: Balance,
+        commission: Balance,
+    }
+    /// A bettor has claimed the winnings of a bet. [matchId, who, amount]
+    #[ink(event)]
+    pub struct WinningsClaimed {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        who: AccountId,
+        amount: Balance,
+    }
+    /// Nobody bet on the result of a match, the bettors can claim their stakes back. [matchId, pot]
+    #[ink(event)]
+    pub struct NoWinnersRefunded {
+        #[ink(topic)]
+        match_id: MatchId,
+        pot: Balance,
+    }
+    /// A match has been cancelled. [matchId, reason, deposit_slashed]
+    #[ink(event)]
+    pub struct MatchCancelled {
+        #[ink(topic)]
+        match_id: MatchId,
+        reason: CancelReason,
+        deposit_slashed: bool,
+    }
+    /// A role has been granted to an account. [role, account, by]
+    #[ink(event)]
+    pub struct RoleGranted {
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+    /// A role has been revoked from an account. [role, account, by]
+    #[ink(event)]
+    pub struct RoleRevoked {
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+        by: AccountId,
+    }
+    /// The ownership of the Smart Contract has changed. [previous_owner, new_owner]
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: Option<AccountId>,
+        #[ink(topic)]
+        new_owner: Option<AccountId>,
+    }
+    /// An admin stopped the changes to all the matches. [by]
+    #[ink(event)]
+    pub struct Paused {
+        by: AccountId,
+    }
+    /// An admin resumed the changes to all the matches. [by]
+    #[ink(event)]
+    pub struct Unpaused {
+        by: AccountId,
+    }
+    /// An admin stopped the changes to a match. [matchId]
+    #[ink(event)]
+    pub struct MatchFrozen {
+        #[ink(topic)]
+        match_id: MatchId,
+    }
+    /// An admin resumed the changes to a match. [matchId]
+    #[ink(event)]
+    pub struct MatchUnfrozen {
+        #[ink(topic)]
+        match_id: MatchId,
+    }
+    /// The code of the Smart Contract has been replaced. [code_hash]
+    #[ink(event)]
+    pub struct Upgraded {
+        code_hash: Hash,
+    }
+    /// The matches and bets have been converted to a new storage version. [version]
+    #[ink(event)]
+    pub struct Migrated {
+        version: u32,
+    }
+    /// The protocol fee has been taken from the pot of a match. [matchId, amount]
+    #[ink(event)]
+    pub struct FeeCollected {
+        #[ink(topic)]
+        match_id: MatchId,
+        amount: Balance,
+    }
+    /// An admin withdrew fees from the treasury. [to, amount, by]
+    #[ink(event)]
+    pub struct FeesWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+        by: AccountId,
+    }
+    /// A bettor got its stake back. [matchId, who, amount]
+    #[ink(event)]
+    pub struct BetRefunded {
+        #[ink(topic)]
+        match_id: MatchId,
+        #[ink(topic)]
+        who: AccountId,
+        amount: Balance,
     }
 
     /// The Betting error types.
@@ -101,8 +791,6 @@ mod betting {
     pub enum Error {
         /// The match to be created already exist.
         MatchAlreadyExists,
-        /// Each account can only have one match open.
-        OriginHasAlreadyOpenMatch,
         /// The time of the match is over.
         TimeMatchOver,
         /// Not enough deposit to create the Match.
@@ -111,14 +799,92 @@ mod betting {
         MatchDoesNotExist,
         /// No allowing betting if the match has started
         MatchHasStarted,
-        /// You already place the same bet in that match
+        /// You already placed the maximum number of bets in that match
         AlreadyBet,
         /// Only owner of the smart contract can make this call
-        BadOrigin,
+        NotOwner,
+        /// Only the pending owner can accept the ownership
+        NotPendingOwner,
+        /// Only an admin of the smart contract can make this call
+        NotAdmin,
+        /// Only a reporter of the match can set its result
+        NotReporter,
+        /// Only the creator of the match can make this call
+        NotMatchCreator,
         /// No allowing set the result if the match not over
         TimeMatchNotOver,
         /// The match still has not a result set
         MatchNotResult,
+        /// The winnings of the match have already been distributed
+        MatchAlreadySettled,
+        /// The match has been cancelled
+        MatchCancelled,
+        /// The result of the match is disputed
+        MatchDisputed,
+        /// The result of the match is not disputed
+        MatchNotDisputed,
+        /// The reporters or the threshold are not valid, or the reporting already started
+        InvalidQuorum,
+        /// The reporter already submitted a result for that match
+        AlreadySubmitted,
+        /// The result of the match has already been agreed
+        ResultAlreadySet,
+        /// The result can still be challenged
+        ChallengePeriodActive,
+        /// The result can not be challenged anymore
+        ChallengePeriodOver,
+        /// Not enough bond to challenge the result
+        NotEnoughBond,
+        /// The winnings of the match have not been distributed yet
+        MatchNotSettled,
+        /// The account has no bet in that match
+        BetDoesNotExist,
+        /// Only the bettor can change its bet
+        NotBettor,
+        /// The bet did not predict the result of the match
+        NotWinningBet,
+        /// The winnings of the bet have already been claimed
+        AlreadyClaimed,
+        /// An arithmetic operation overflowed
+        Overflow,
+        /// The Smart Contract is paused
+        Paused,
+        /// The match is frozen
+        MatchFrozen,
+        /// The code of the Smart Contract could not be replaced
+        UpgradeFailed,
+        /// The storage is already in the latest version
+        AlreadyMigrated,
+        /// The fee is higher than `MAX_FEE_BPS`
+        FeeTooHigh,
+        /// The commission is higher than `MAX_COMMISSION_BPS`
+        CommissionTooHigh,
+        /// The minimum stake is higher than the maximum or no bet is allowed
+        InvalidBetLimits,
+        /// The stake is zero or lower than the minimum of the match or of the Smart Contract
+        BetTooSmall,
+        /// The stake is higher than the maximum of the match or of the Smart Contract
+        BetTooLarge,
+        /// Not enough fees in the treasury
+        InsufficientTreasury,
+        /// The name of a team is empty
+        EmptyTeamName,
+        /// The name of a team is longer than `MAX_TEAM_NAME_LEN`
+        TeamNameTooLong,
+        /// The name of a team is not valid UTF-8
+        InvalidTeamName,
+        /// Both teams of the match have the same name
+        SameTeams,
+        /// The market has not between 2 and `MAX_OUTCOMES` outcomes or its line is not a half point
+        InvalidMarket,
+        /// The outcome does not exist in the market of the match
+        InvalidOutcome,
+        /// There are not odds above 1 for every outcome
+        InvalidOdds,
+        /// The match has not fixed odds
+        NotFixedOdds,
+        /// The deposit of the creator can not cover the payout of the bet
+        InsufficientCollateral,
          /// Returned if the requested transfer failed. This can be the case if the
         /// contract does not have sufficient free funds or if the transfer would
         /// have brought the contract's balance below minimum balance.
@@ -126,70 +892,120 @@ mod betting {
     }
 
     impl Betting {
+        /// Creates the Smart Contract, taking `fee_bps` basis points of each settled pot.
         #[ink(constructor, payable)]
-        pub fn new() -> Self {
+        pub fn new(fee_bps: u16) -> Result<Self, Error> {
+            if fee_bps > MAX_FEE_BPS {
+                return Err(Error::FeeTooHigh);
+            }
             let owner = Self::env().caller();
-            Self {
+            Ok(Self {
                 matches: Default::default(),
                 matches_hashes: Default::default(),
-                owner,
-            }
+                bets: Default::default(),
+                bettors: Default::default(),
+                creator_matches: Default::default(),
+                creator_matches_count: Default::default(),
+                index: Default::default(),
+                index_len: Default::default(),
+                index_position: Default::default(),
+                positions: Default::default(),
+                positions_count: Default::default(),
+                position_of: Default::default(),
+                metadata: Default::default(),
+                next_match_id: 0,
+                owner: Some(owner),
+                pending_owner: None,
+                roles: Default::default(),
+                quorums: Default::default(),
+                submissions: Default::default(),
+                challenges: Default::default(),
+                paused: false,
+                challenge_period: 0,
+                dispute_bond: 0,
+                no_winners_policy: Default::default(),
+                version: STORAGE_VERSION,
+                migrated_until: 0,
+                fee_bps,
+                treasury: 0,
+                min_deposit: MIN_DEPOSIT,
+                min_stake: MIN_STAKE,
+                max_stake: Balance::MAX,
+            })
         }
 
         // payable accepts a payment (deposit).
         #[ink(message, payable)]
+        #[allow(clippy::too_many_arguments)]
         pub fn create_match_to_bet(
             &mut self, 
             team1: Vec<u8>,
             team2: Vec<u8>,
             start: BlockNumber,
-            length: BlockNumber
-        ) -> Result<(), Error> {
+            length: BlockNumber,
+            market: Market,
+            commission_bps: u16,
+            bet_limits: BetLimits,
+            metadata: MatchMetadata,
+        ) -> Result<MatchId, Error> {
+            let mut betting_match = self.new_match(team1, team2, start, length, market, bet_limits)?;
+            if commission_bps > MAX_COMMISSION_BPS {
+                return Err(Error::CommissionTooHigh)
+            }
+            betting_match.commission_bps = commission_bps;
+            self.insert_match(betting_match, metadata)
+        }
+
+        /// Creates a match where the bets are paid at the `odds` offered by the creator
+        /// when they are placed, instead of sharing the pot. The deposit covers the payouts.
+        #[ink(message, payable)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_fixed_odds_match(
+            &mut self,
+            team1: Vec<u8>,
+            team2: Vec<u8>,
+            start: BlockNumber,
+            length: BlockNumber,
+            market: Market,
+            bet_limits: BetLimits,
+            odds: Vec<u32>,
+            metadata: MatchMetadata,
+        ) -> Result<MatchId, Error> {
+            let mut betting_match = self.new_match(team1, team2, start, length, market, bet_limits)?;
+            Self::ensure_valid_odds(&odds, &betting_match.market)?;
+            betting_match.odds = Some(odds);
+            self.insert_match(betting_match, metadata)
+        }
+
+        /// Publish new odds for a fixed odds match, before it starts.
+        /// The bets already placed keep their odds.
+        #[ink(message)]
+        pub fn update_odds(&mut self, match_id: MatchId, odds: Vec<u32>) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // Check account has no open match
-            if self.exists_match(caller) {
-                return Err(Error::OriginHasAlreadyOpenMatch)
+            self.ensure_not_paused()?;
+            let mut match_to_update = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            Self::ensure_not_frozen(&match_to_update)?;
+            if caller != match_to_update.creator {
+                return Err(Error::NotMatchCreator);
             }
-            // Check if start and length are valid
-            let current_block_number = self.env().block_number();
-            if current_block_number > (start + length) {
-                return Err(Error::TimeMatchOver)
+            Self::ensure_open(&match_to_update)?;
+            if self.env().block_number() > match_to_update.start {
+                return Err(Error::MatchHasStarted)
             }
-            // Check the deposit.
-            // Assert or Error?
-            let deposit = Self::env().transferred_value();
-            if deposit < MIN_DEPOSIT {
-                return Err(Error::NotEnoughDeposit)
-            }
-            // Create the betting match
-            let betting_match = Match {
-                start,
-                length,
-                team1,
-                team2,
-                result: None,
-                bets: Default::default(),
-                deposit,
-            };
-            // Hash the match object.
-            let match_hash = Self::get_match_hash(&betting_match);
-            // Check if match already exists by checking its specs hash.
-            if self.matches_hashes.contains(match_hash) {
-                return Err(Error::MatchAlreadyExists)
+            if match_to_update.odds.is_none() {
+                return Err(Error::NotFixedOdds);
             }
-            // Store the match hash with its creator account.
-            self.matches_hashes.insert(match_hash, &caller);
-            // Store the betting match in the list of open matches
-            self.matches.insert(caller, &betting_match);
+            Self::ensure_valid_odds(&odds, &match_to_update.market)?;
+            match_to_update.odds = Some(odds.clone());
+            self.matches.insert(match_id, &match_to_update);
             // Emit an event.
-            self.env().emit_event(MatchCreated {
-                who: caller,
-                team1: betting_match.team1,
-                team2: betting_match.team2,
-                start,
-                length,
+            self.env().emit_event(OddsUpdated {
+                match_id,
+                odds,
             });
-
             Ok(())
         }
 
@@ -197,73 +1013,208 @@ mod betting {
         #[ink(message, payable)]
         pub fn bet(
             &mut self, 
-            match_id: AccountId,
-            result: MatchResult,
+            match_id: MatchId,
+            result: Outcome,
         ) -> Result<(), Error> {
             let caller = Self::env().caller();
+            self.ensure_not_paused()?;
             // Find the match that user wants to place the bet
-            let mut match_to_bet = match self.matches.take(&match_id) {
+            let mut match_to_bet = match self.matches.get(match_id) {
                 Some(match_from_storage) => match_from_storage,
                 None => return Err(Error::MatchDoesNotExist)
             };
+            Self::ensure_not_frozen(&match_to_bet)?;
 
+            Self::ensure_open(&match_to_bet)?;
+            Self::ensure_outcome(&match_to_bet, result)?;
             // Check if the Match Has Started (can't bet in a started match)
             let current_block_number = self.env().block_number();
             if current_block_number > match_to_bet.start {
                 return Err(Error::MatchHasStarted)
             }
+            // Check if the account can place another bet in this match
+            let mut bet_ids = self.bettors.get((match_id, caller)).unwrap_or_default();
+            if bet_ids.len() >= match_to_bet.bet_limits.max_bets as usize {
+                return Err(Error::AlreadyBet);
+            }
             let amount = Self::env().transferred_value();
-            // Create the bet to be placed
+            if amount == 0 || amount < match_to_bet.bet_limits.min_stake || amount < self.min_stake {
+                return Err(Error::BetTooSmall);
+            }
+            if amount > match_to_bet.bet_limits.max_stake || amount > self.max_stake {
+                return Err(Error::BetTooLarge);
+            }
+            // Create the bet to be placed, locking the current odds of a fixed odds match
             let bet = Bet {
                 bettor: caller,
                 amount,
-                result: result.clone(),
+                result,
+                odds: match_to_bet.odds.as_ref().map(|odds| odds[result as usize]),
+                claimed: false,
             };
-            // Check if the bet already exists
-            if match_to_bet.bets.contains(&bet) { 
-                return Err(Error::AlreadyBet);
+            // Make sure the pot of the match does not overflow
+            match_to_bet.totals.iter().try_fold(amount, |pot, total| pot.checked_add(*total))
+                .ok_or(Error::Overflow)?;
+            // Update the running totals of the match
+            match_to_bet.bets_count += 1;
+            match_to_bet.totals[result as usize] += amount;
+            match_to_bet.payouts[result as usize] += Self::fixed_payout(&bet)?;
+            Self::ensure_collateral(&match_to_bet)?;
+            let bet_id = match_to_bet.bets_count - 1;
+            self.bets.insert((match_id, bet_id), &bet);
+            bet_ids.push(bet_id);
+            self.add_position(caller, match_id, bet_id);
+            self.bettors.insert((match_id, caller), &bet_ids);
+            self.matches.insert(match_id, &match_to_bet);
+            // Emit an event.
+            self.env().emit_event(BetPlaced {
+                match_id,
+                who: caller,
+                bet_id,
+                amount,
+                result
+            });
+            Ok(())
+        }
+
+        /// Add the transferred value to the stake of a bet, before the match starts.
+        #[ink(message, payable)]
+        pub fn increase_bet(&mut self, match_id: MatchId, bet_id: BetId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let (mut match_to_bet, mut bet) = self.bet_to_change(match_id, bet_id)?;
+            let amount = Self::env().transferred_value();
+            if amount == 0 {
+                return Err(Error::BetTooSmall);
+            }
+            let total = bet.amount.checked_add(amount).ok_or(Error::Overflow)?;
+            if total > match_to_bet.bet_limits.max_stake || total > self.max_stake {
+                return Err(Error::BetTooLarge);
+            }
+            // Make sure the pot of the match does not overflow
+            match_to_bet.totals.iter().try_fold(amount, |pot, total| pot.checked_add(*total))
+                .ok_or(Error::Overflow)?;
+            match_to_bet.payouts[bet.result as usize] -= Self::fixed_payout(&bet)?;
+            bet.amount = total;
+            match_to_bet.totals[bet.result as usize] += amount;
+            match_to_bet.payouts[bet.result as usize] += Self::fixed_payout(&bet)?;
+            Self::ensure_collateral(&match_to_bet)?;
+            self.bets.insert((match_id, bet_id), &bet);
+            self.matches.insert(match_id, &match_to_bet);
+            // Emit an event.
+            self.env().emit_event(BetIncreased {
+                match_id,
+                who: caller,
+                bet_id,
+                amount,
+                total,
+            });
+            Ok(())
+        }
+
+        /// Change the result predicted by a bet, before the match starts.
+        /// In a fixed odds match the bet gets the current odds of the new result.
+        #[ink(message)]
+        pub fn change_prediction(&mut self, match_id: MatchId, bet_id: BetId, result: Outcome) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let (mut match_to_bet, mut bet) = self.bet_to_change(match_id, bet_id)?;
+            Self::ensure_outcome(&match_to_bet, result)?;
+            match_to_bet.totals[bet.result as usize] -= bet.amount;
+            match_to_bet.payouts[bet.result as usize] -= Self::fixed_payout(&bet)?;
+            bet.result = result;
+            bet.odds = match_to_bet.odds.as_ref().map(|odds| odds[result as usize]);
+            match_to_bet.totals[result as usize] += bet.amount;
+            match_to_bet.payouts[result as usize] += Self::fixed_payout(&bet)?;
+            Self::ensure_collateral(&match_to_bet)?;
+            self.matches.insert(match_id, &match_to_bet);
+            self.bets.insert((match_id, bet_id), &bet);
+            // Emit an event.
+            self.env().emit_event(PredictionChanged {
+                match_id,
+                who: caller,
+                bet_id,
+                result,
+            });
+            Ok(())
+        }
+
+        /// Withdraw a bet and get its stake back, before the match starts.
+        #[ink(message)]
+        pub fn cancel_bet(&mut self, match_id: MatchId, bet_id: BetId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let (mut match_to_bet, bet) = self.bet_to_change(match_id, bet_id)?;
+            match_to_bet.totals[bet.result as usize] -= bet.amount;
+            match_to_bet.payouts[bet.result as usize] -= Self::fixed_payout(&bet)?;
+            // The stake may be needed to cover the payouts of the other results
+            Self::ensure_collateral(&match_to_bet)?;
+            self.matches.insert(match_id, &match_to_bet);
+            self.bets.remove((match_id, bet_id));
+            self.remove_position(caller, match_id, bet_id);
+            // Free the slot of the bet, the account can place another one
+            let mut bet_ids = self.bettors.get((match_id, caller)).unwrap_or_default();
+            bet_ids.retain(|id| *id != bet_id);
+            if bet_ids.is_empty() {
+                self.bettors.remove((match_id, caller));
             } else {
-                match_to_bet.bets.push(bet);
-                // Store the betting match in the list of open matches
-                self.matches.insert(match_id, &match_to_bet);
-                // Emit an event.
-                self.env().emit_event(BetPlaced {
-                    match_id,
-                    who: caller,
-                    amount,
-                    result
-                });
+                self.bettors.insert((match_id, caller), &bet_ids);
             }
+            self.env().transfer(caller, bet.amount).map_err(|_| Error::TransferFailed)?;
+            // Emit an event.
+            self.env().emit_event(BetCancelled {
+                match_id,
+                who: caller,
+                bet_id,
+                amount: bet.amount,
+            });
             Ok(())
         }
 
 
         /// Set the result of an existing match.
-        /// The dispatch origin for this call must be the owner.
-        /// Get root of the node?? like ensure_root(origin)?;
+        /// The dispatch origin for this call must be a reporter of all the matches or of this match.
+        /// If the match has a quorum only its reporters can submit a result, which is set once
+        /// enough of them agree.
         #[ink(message)]
         pub fn set_result(
             &mut self, 
-            match_id: AccountId,
-            result: MatchResult,
+            match_id: MatchId,
+            result: Outcome,
         ) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // Only owner of the SC can call this message.
-            if caller != self.owner {
-                return Err(Error::BadOrigin);
+            self.ensure_not_paused()?;
+            let quorum = self.quorums.get(match_id);
+            // Only reporters can call this message.
+            let is_reporter = match &quorum {
+                Some(quorum) => quorum.reporters.contains(&caller),
+                None => self.has_role(Role::Reporter, caller)
+                    || self.has_role(Role::MatchReporter(match_id), caller),
+            };
+            if !is_reporter {
+                return Err(Error::NotReporter);
             }
             //Find the match where owner wants to set the result
-            let mut match_to_set_result = match self.matches.take(&match_id) {
+            let mut match_to_set_result = match self.matches.get(match_id) {
                 Some(match_from_storage) => match_from_storage,
                 None => return Err(Error::MatchDoesNotExist)
             };
+            Self::ensure_not_frozen(&match_to_set_result)?;
+            // The result can not change once the winnings are distributed
+            Self::ensure_open(&match_to_set_result)?;
+            // Once set, only an admin ruling a dispute can change the result
+            if match_to_set_result.result.is_some() {
+                return Err(Error::ResultAlreadySet);
+            }
+            Self::ensure_outcome(&match_to_set_result, result)?;
             // Check if start and length are valid
             let current_block_number = self.env().block_number();
             if current_block_number <= (match_to_set_result.start + match_to_set_result.length) {
                 return Err(Error::TimeMatchNotOver)
             }
+            if let Some(quorum) = quorum {
+                return self.submit_result(match_id, match_to_set_result, quorum, caller, result);
+            }
             //set the result
-            match_to_set_result.result = Some(result.clone());
+            match_to_set_result.result = Some(result);
+            match_to_set_result.challenge_end = current_block_number + self.challenge_period;
             // Store the betting match in the list of open matches
             self.matches.insert(match_id, &match_to_set_result);
             // Emit an event.
@@ -275,72 +1226,1355 @@ mod betting {
             Ok(())
         }
 
-         /// When a match ends the owner of the match can distribute funds to the winners and delete the match.
+        /// Assign a quorum of reporters to a match, replacing the reporter roles for it.
+        /// The dispatch origin for this call must be an admin.
         #[ink(message)]
-        pub fn distribute_winnings(&mut self) -> Result<(), Error> {
+        pub fn assign_reporters(
+            &mut self,
+            match_id: MatchId,
+            reporters: Vec<AccountId>,
+            threshold: u32,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let betting_match = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            Self::ensure_open(&betting_match)?;
+            if betting_match.result.is_some() {
+                return Err(Error::ResultAlreadySet);
+            }
+            // The threshold must be reachable and the reporters unique
+            if threshold == 0 || threshold as usize > reporters.len() {
+                return Err(Error::InvalidQuorum);
+            }
+            for (i, reporter) in reporters.iter().enumerate() {
+                if reporters[..i].contains(reporter) {
+                    return Err(Error::InvalidQuorum);
+                }
+            }
+            // Can not be replaced once a reporter submitted a result
+            if let Some(quorum) = self.quorums.get(match_id) {
+                if quorum.submitted > 0 {
+                    return Err(Error::InvalidQuorum);
+                }
+            }
+            self.quorums.insert(match_id, &Quorum {
+                reporters,
+                threshold,
+                submitted: 0,
+                votes: vec![0; betting_match.market.outcomes() as usize],
+            });
+            Ok(())
+        }
+
+        /// Challenge the result of a match during the challenge period, posting a bond.
+        /// The match is disputed until an admin rules on it with `resolve_dispute`.
+        /// It works while the Smart Contract is paused or the match frozen, since the challenge
+        /// period keeps running.
+        /// The dispatch origin for this call must be a bettor of the match.
+        #[ink(message, payable)]
+        pub fn dispute_result(&mut self, match_id: MatchId) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // Get the match that user wants to close, deleting it
-             let mut match_to_delete = match self.matches.take(&caller) {
+            let mut challenged_match = match self.matches.get(match_id) {
                 Some(match_from_storage) => match_from_storage,
                 None => return Err(Error::MatchDoesNotExist)
             };
-            // Make sure the match has a result set already
-            if !match_to_delete.result.is_some() {
+            Self::ensure_open(&challenged_match)?;
+            if challenged_match.result.is_none() {
                 return Err(Error::MatchNotResult);
             }
+            if self.env().block_number() >= challenged_match.challenge_end {
+                return Err(Error::ChallengePeriodOver);
+            }
+            if !self.bettors.contains((match_id, caller)) {
+                return Err(Error::BetDoesNotExist);
+            }
+            let bond = Self::env().transferred_value();
+            if bond < self.dispute_bond {
+                return Err(Error::NotEnoughBond);
+            }
+            challenged_match.status = MatchStatus::Disputed;
+            self.matches.insert(match_id, &challenged_match);
+            self.challenges.insert(match_id, &Challenge {
+                challenger: caller,
+                bond,
+            });
+            self.env().emit_event(ResultChallenged {
+                match_id,
+                challenger: caller,
+                bond,
+            });
+            Ok(())
+        }
+
+        /// Set the final result of a disputed match. If the match was challenged, the bond
+        /// is returned when the result changes and slashed otherwise.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, match_id: MatchId, result: Outcome) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let mut disputed_match = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            if disputed_match.status != MatchStatus::Disputed {
+                return Err(Error::MatchNotDisputed);
+            }
+            Self::ensure_outcome(&disputed_match, result)?;
+            let proposed_result = disputed_match.result;
+            // The ruling is final, it can not be challenged
+            disputed_match.result = Some(result);
+            disputed_match.challenge_end = self.env().block_number();
+            disputed_match.status = MatchStatus::Open;
+            self.matches.insert(match_id, &disputed_match);
+            self.env().emit_event(MatchResultSet {
+                match_id,
+                result
+            });
+            if let Some(challenge) = self.challenges.take(match_id) {
+                let upheld = proposed_result != Some(result);
+                if upheld {
+                    self.env().transfer(challenge.challenger, challenge.bond).map_err(|_| Error::TransferFailed)?;
+                } else {
+                    self.pay_house(challenge.bond);
+                }
+                self.env().emit_event(ChallengeResolved {
+                    match_id,
+                    challenger: challenge.challenger,
+                    upheld,
+                });
+            }
+            Ok(())
+        }
+
+         /// When a match ends the owner of the match can settle it, recording the pot to be
+        /// claimed by the winners and getting the deposit back.
+        #[ink(message)]
+        pub fn distribute_winnings(&mut self, match_id: MatchId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.ensure_not_paused()?;
+            // Get the match that user wants to close
+            let mut match_to_settle = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            Self::ensure_not_frozen(&match_to_settle)?;
+            // Only the creator of the match can close it
+            if caller != match_to_settle.creator {
+                return Err(Error::NotMatchCreator);
+            }
+            Self::ensure_open(&match_to_settle)?;
+            // Make sure the match has a result set already
+            let result = match match_to_settle.result {
+                Some(result) => result,
+                None => return Err(Error::MatchNotResult),
+            };
+            // Wait until the result can not be challenged anymore
+            if self.env().block_number() < match_to_settle.challenge_end {
+                return Err(Error::ChallengePeriodActive);
+            }
             // Hash the match object and delete it from the hash mapping.
-            let match_hash = Self::get_match_hash(&match_to_delete);
-            self.matches_hashes.take(&match_hash);
-            // Iterate over all bets to get the winners accounts
-            let mut total_winners: Balance = 0u32.into();
-            let mut total_bet: Balance = 0u32.into();
-            let mut winners = Vec::new();
-            for bet in match_to_delete.bets.iter_mut() {
-                total_bet += bet.amount;
-                if Some(bet.result) == match_to_delete.result {
-                    total_winners += bet.amount;
-                    winners.push(bet)
+            let match_hash = self.match_hash(match_id, &match_to_settle);
+            self.matches_hashes.take(match_hash);
+            // Record the pot, the winners claim their share of it
+            let winning_total = match_to_settle.totals[result as usize];
+            let total_bet: Balance = match_to_settle.totals.iter().sum();
+            match_to_settle.pot = total_bet;
+            match_to_settle.status = MatchStatus::Settled;
+            let mut house_take = 0;
+            let mut fee = 0;
+            let mut commission = 0;
+            let mut creator_take = match_to_settle.deposit;
+            if match_to_settle.odds.is_some() {
+                // The winners get their locked payouts, the creator keeps the rest of the
+                // stakes and of its deposit, after the protocol fee on its margin
+                match_to_settle.pot = match_to_settle.payouts[result as usize];
+                fee = mul_div(total_bet.saturating_sub(match_to_settle.pot), self.fee_bps.into(), BPS_DENOMINATOR)
+                    .ok_or(Error::Overflow)?;
+                creator_take = creator_take + total_bet - match_to_settle.pot - fee;
+            } else {
+                // Apply the no winners policy if nobody bet on the result
+                if winning_total == 0 && match_to_settle.pot > 0 {
+                    match self.no_winners_policy {
+                        NoWinnersPolicy::Refund => match_to_settle.refunds = true,
+                        NoWinnersPolicy::House => house_take = core::mem::take(&mut match_to_settle.pot),
+                    }
+                }
+                // Take the protocol fee and the commission of the creator from the pot of the winners
+                if winning_total > 0 {
+                    (fee, commission) = self.pot_cuts(&match_to_settle, match_to_settle.pot)?;
+                }
+                match_to_settle.pot -= fee + commission;
+                creator_take += commission;
+            }
+            self.treasury += fee;
+            self.matches.insert(match_id, &match_to_settle);
+            self.move_index(match_id, MatchIndex::Active, MatchIndex::Settled);
+            // Return deposit along with the commission
+            self.env().transfer(caller, creator_take).map_err(|_| Error::TransferFailed)?;
+            if house_take > 0 {
+                self.pay_house(house_take);
+            }
+            // Emit an event.
+            if fee > 0 {
+                self.env().emit_event(FeeCollected {
+                    match_id,
+                    amount: fee,
+                });
+            }
+            self.env().emit_event(MatchSettled {
+                match_id,
+                pot: match_to_settle.pot,
+                winning_total,
+                commission,
+            });
+            if match_to_settle.refunds {
+                self.env().emit_event(NoWinnersRefunded {
+                    match_id,
+                    pot: match_to_settle.pot,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Once a match is settled each winner claims its share of the pot,
+        /// or each bettor its stake if the pot is refunded or the match cancelled.
+        #[ink(message)]
+        pub fn claim_winnings(&mut self, match_id: MatchId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.ensure_not_paused()?;
+            let mut settled_match = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            Self::ensure_not_frozen(&settled_match)?;
+            // The bets are claimed once the match is settled or cancelled
+            match settled_match.status {
+                MatchStatus::Settled | MatchStatus::Cancelled => {}
+                MatchStatus::Disputed => return Err(Error::MatchDisputed),
+                MatchStatus::Open => return Err(Error::MatchNotSettled),
+            }
+            // Find the bets of the caller
+            let bet_ids = match self.bettors.get((match_id, caller)) {
+                Some(bet_ids) => bet_ids,
+                None => return Err(Error::BetDoesNotExist)
+            };
+            // Mark the winning bets as claimed before transferring
+            let mut has_winning_bet = false;
+            let mut stake = 0;
+            let mut fixed_payout = 0;
+            for bet_id in bet_ids {
+                let mut bet = match self.bets.get((match_id, bet_id)) {
+                    Some(bet_from_storage) => bet_from_storage,
+                    None => return Err(Error::BetDoesNotExist)
+                };
+                if !settled_match.refunds && Some(bet.result) != settled_match.result {
+                    continue;
                 }
+                has_winning_bet = true;
+                if bet.claimed {
+                    continue;
+                }
+                bet.claimed = true;
+                self.bets.insert((match_id, bet_id), &bet);
+                stake += bet.amount;
+                fixed_payout += Self::fixed_payout(&bet)?;
+            }
+            if !has_winning_bet {
+                return Err(Error::NotWinningBet);
             }
-            // Distribute funds
-            for winner_bet in &winners {
-                let weighted = winner_bet.amount / (total_winners / 100);
-                let amount_won = weighted * (total_bet /100);
-                self.env().transfer(winner_bet.bettor, amount_won).map_err(|_| Error::TransferFailed)?;
+            if stake == 0 {
+                return Err(Error::AlreadyClaimed);
+            }
+            // The stakes are given back if the pot is refunded
+            if settled_match.refunds {
+                self.env().transfer(caller, stake).map_err(|_| Error::TransferFailed)?;
+                self.env().emit_event(BetRefunded {
+                    match_id,
+                    who: caller,
+                    amount: stake,
+                });
+                return Ok(());
+            }
+            // Weighted share of the pot
+            let result = settled_match.result.ok_or(Error::MatchNotResult)?;
+            let total_winners = settled_match.totals[result as usize];
+            let amount_won = if settled_match.odds.is_some() {
+                fixed_payout
+            } else {
+                Self::calculate_payout(stake, settled_match.pot, total_winners).ok_or(Error::Overflow)?
+            };
+            settled_match.claimed_stake += stake;
+            settled_match.paid_out += amount_won;
+            // The last winner to claim closes the pot, the rounding remainder goes to the creator
+            let remainder = if settled_match.claimed_stake == total_winners {
+                settled_match.pot - settled_match.paid_out
+            } else {
+                0
+            };
+            self.matches.insert(match_id, &settled_match);
+            self.env().transfer(caller, amount_won).map_err(|_| Error::TransferFailed)?;
+            if remainder > 0 {
+                self.env().transfer(settled_match.creator, remainder).map_err(|_| Error::TransferFailed)?;
             }
-            // Return deposit
-            self.env().transfer(caller, match_to_delete.deposit).map_err(|_| Error::TransferFailed)?;
+            // Emit an event.
+            self.env().emit_event(WinningsClaimed {
+                match_id,
+                who: caller,
+                amount: amount_won,
+            });
 
             Ok(())
         }
 
+        /// Void a match, its bettors can claim their stakes back.
+        /// The creator of the match can cancel it before it starts and an admin
+        /// at any time before it is settled. The deposit is returned to the creator
+        /// unless an admin cancels it as `Invalid`.
+        #[ink(message)]
+        pub fn cancel_match(&mut self, match_id: MatchId, reason: CancelReason) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut match_to_cancel = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            // A disputed match can be voided instead of resolved
+            if match_to_cancel.status != MatchStatus::Disputed {
+                Self::ensure_open(&match_to_cancel)?;
+            }
+            let is_admin = self.has_role(Role::Admin, caller);
+            if !is_admin {
+                self.ensure_not_paused()?;
+                Self::ensure_not_frozen(&match_to_cancel)?;
+                if caller != match_to_cancel.creator {
+                    return Err(Error::NotMatchCreator);
+                }
+                if self.env().block_number() > match_to_cancel.start {
+                    return Err(Error::MatchHasStarted);
+                }
+            }
+            // Hash the match object and delete it from the hash mapping.
+            let match_hash = self.match_hash(match_id, &match_to_cancel);
+            self.matches_hashes.take(match_hash);
+            match_to_cancel.status = MatchStatus::Cancelled;
+            match_to_cancel.refunds = true;
+            self.matches.insert(match_id, &match_to_cancel);
+            self.move_index(match_id, MatchIndex::Active, MatchIndex::Cancelled);
+            // Return or slash the deposit
+            let deposit_slashed = is_admin && reason == CancelReason::Invalid;
+            if deposit_slashed {
+                self.pay_house(match_to_cancel.deposit);
+            } else {
+                self.env().transfer(match_to_cancel.creator, match_to_cancel.deposit).map_err(|_| Error::TransferFailed)?;
+            }
+            // A pending challenge gets its bond back
+            if let Some(challenge) = self.challenges.take(match_id) {
+                self.env().transfer(challenge.challenger, challenge.bond).map_err(|_| Error::TransferFailed)?;
+            }
+            // Emit an event.
+            self.env().emit_event(MatchCancelled {
+                match_id,
+                reason,
+                deposit_slashed,
+            });
+
+            Ok(())
+        }
 
-        /// Simply checks if a match exists.
+        /// Set what happens with the pot of the matches without winners.
+        /// The dispatch origin for this call must be an admin.
         #[ink(message)]
-        pub fn exists_match(&self, owner: AccountId) -> bool {
-            self.matches.contains(owner)
+        pub fn set_no_winners_policy(&mut self, policy: NoWinnersPolicy) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.no_winners_policy = policy;
+            Ok(())
         }
+
         #[ink(message)]
-        pub fn get_match(&self, owner: AccountId) -> Option<Match> {
-            self.matches.get(owner)
+        pub fn no_winners_policy(&self) -> NoWinnersPolicy {
+            self.no_winners_policy
         }
 
-        pub fn get_match_hash(
-            betting_match: &Match,
-        ) -> [u8;32] {
-            let entropy = (
-                &betting_match.team1,
-                &betting_match.team2,
-                betting_match.start,
-                betting_match.length,
-            );
-            let mut message =
-                <ink::env::hash::Sha2x256 as ink::env::hash::HashOutput>::Type::default();
-            ink::env::hash_encoded::<ink::env::hash::Sha2x256, _>(
-                &entropy,
-                &mut message,
-            );
-            message
+        /// Set the protocol fee taken from the settled pots, in basis points.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee_bps: u16) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if fee_bps > MAX_FEE_BPS {
+                return Err(Error::FeeTooHigh);
+            }
+            self.fee_bps = fee_bps;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee(&self) -> u16 {
+            self.fee_bps
+        }
+
+        /// Send `amount` of the treasury, the collected fees and funds of the house, to `to`.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn withdraw_fees(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if amount > self.treasury {
+                return Err(Error::InsufficientTreasury);
+            }
+            self.treasury -= amount;
+            self.env().transfer(to, amount).map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(FeesWithdrawn {
+                to,
+                amount,
+                by: Self::env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn treasury(&self) -> Balance {
+            self.treasury
+        }
+
+        /// Stop the changes to all the matches, only the admins can still cancel matches
+        /// and resolve disputes, and the bettors dispute results.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.paused = true;
+            self.env().emit_event(Paused {
+                by: Self::env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Resume the changes to all the matches.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.paused = false;
+            self.env().emit_event(Unpaused {
+                by: Self::env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Stop the changes to a match, only the admins can still cancel it and resolve its dispute,
+        /// and the bettors dispute its result.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn freeze_match(&mut self, match_id: MatchId) -> Result<(), Error> {
+            self.set_frozen(match_id, true)?;
+            self.env().emit_event(MatchFrozen { match_id });
+            Ok(())
+        }
+
+        /// Resume the changes to a match.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn unfreeze_match(&mut self, match_id: MatchId) -> Result<(), Error> {
+            self.set_frozen(match_id, false)?;
+            self.env().emit_event(MatchUnfrozen { match_id });
+            Ok(())
+        }
+
+        /// Replace the code of the Smart Contract, keeping its storage and balance.
+        /// If the new code changes the storage version, pause the Smart Contract
+        /// and call `migrate` until every match is converted.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.env().set_code_hash(&code_hash).map_err(|_| Error::UpgradeFailed)?;
+            self.env().emit_event(Upgraded { code_hash });
+            Ok(())
+        }
+
+        /// Convert up to `limit` matches stored in the version 1 layout, with their bets, continuing
+        /// where the previous call stopped. The storage version is updated once every match is converted.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn migrate(&mut self, limit: u32) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if self.version >= STORAGE_VERSION {
+                return Err(Error::AlreadyMigrated);
+            }
+            let matches_key = self.matches.key();
+            let until = self.next_match_id.min(self.migrated_until.saturating_add(limit));
+            for match_id in self.migrated_until..until {
+                let legacy_match = ink::env::get_contract_storage::<_, MatchV1>(&(&matches_key, match_id))
+                    .unwrap_or_default();
+                if let Some(legacy_match) = legacy_match {
+                    self.migrate_match(match_id, legacy_match)?;
+                }
+            }
+            self.migrated_until = until;
+            if until == self.next_match_id {
+                self.version = STORAGE_VERSION;
+                self.env().emit_event(Migrated { version: STORAGE_VERSION });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            self.version
+        }
+
+        /// Set the blocks during which a result can be challenged and the bond to do it.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn set_challenge_period(&mut self, challenge_period: BlockNumber, dispute_bond: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.challenge_period = challenge_period;
+            self.dispute_bond = dispute_bond;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn challenge_period(&self) -> BlockNumber {
+            self.challenge_period
+        }
+
+        #[ink(message)]
+        pub fn dispute_bond(&self) -> Balance {
+            self.dispute_bond
+        }
+
+        /// Set the minimum deposit to create a match and the smallest and largest stake accepted by any match.
+        /// The dispatch origin for this call must be an admin.
+        #[ink(message)]
+        pub fn set_limits(&mut self, min_deposit: Balance, min_stake: Balance, max_stake: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if min_stake > max_stake {
+                return Err(Error::InvalidBetLimits)
+            }
+            self.min_deposit = min_deposit;
+            self.min_stake = min_stake;
+            self.max_stake = max_stake;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_deposit(&self) -> Balance {
+            self.min_deposit
+        }
+
+        #[ink(message)]
+        pub fn min_stake(&self) -> Balance {
+            self.min_stake
+        }
+
+        #[ink(message)]
+        pub fn max_stake(&self) -> Balance {
+            self.max_stake
+        }
+
+        /// Grant a role to an account.
+        /// Only the owner can grant the `Admin` role, the admins grant the rest of the roles.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.ensure_can_manage(role)?;
+            self.roles.insert((account, role), &());
+            self.env().emit_event(RoleGranted {
+                role,
+                account,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        /// Revoke a role from an account.
+        /// Only the owner can revoke the `Admin` role, the admins revoke the rest of the roles.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.ensure_can_manage(role)?;
+            self.roles.remove((account, role));
+            self.env().emit_event(RoleRevoked {
+                role,
+                account,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        /// Checks if an account holds a role, the owner holds all of them.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            Some(account) == self.owner || self.roles.contains((account, role))
+        }
+
+        /// Start the transfer of the ownership, `new_owner` has to accept it.
+        /// The dispatch origin for this call must be the owner.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.pending_owner = Some(new_owner);
+            Ok(())
+        }
+
+        /// Accept the ownership of the Smart Contract.
+        /// The dispatch origin for this call must be the pending owner.
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.pending_owner != Some(caller) {
+                return Err(Error::NotPendingOwner);
+            }
+            let previous_owner = self.owner;
+            self.owner = Some(caller);
+            self.pending_owner = None;
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner: self.owner,
+            });
+            Ok(())
+        }
+
+        /// Leave the Smart Contract without owner, the admins keep their role.
+        /// The dispatch origin for this call must be the owner.
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let previous_owner = self.owner;
+            self.owner = None;
+            self.pending_owner = None;
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner: None,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> Option<AccountId> {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner
+        }
+
+        /// Simply checks if a match exists.
+        #[ink(message)]
+        pub fn exists_match(&self, match_id: MatchId) -> bool {
+            self.matches.contains(match_id)
+        }
+        #[ink(message)]
+        pub fn get_match(&self, match_id: MatchId) -> Option<Match> {
+            self.matches.get(match_id)
+        }
+        #[ink(message)]
+        pub fn get_challenge(&self, match_id: MatchId) -> Option<Challenge> {
+            self.challenges.get(match_id)
+        }
+        #[ink(message)]
+        pub fn get_quorum(&self, match_id: MatchId) -> Option<Quorum> {
+            self.quorums.get(match_id)
+        }
+        /// Returns the summary of a match.
+        #[ink(message)]
+        pub fn match_view(&self, match_id: MatchId) -> Option<MatchView> {
+            let betting_match = self.matches.get(match_id)?;
+            let phase = Self::phase_of(&betting_match, self.env().block_number());
+            Some(MatchView {
+                version: MATCH_VIEW_VERSION,
+                match_id,
+                creator: betting_match.creator,
+                start: betting_match.start,
+                end: betting_match.start.saturating_add(betting_match.length),
+                challenge_end: betting_match.challenge_end,
+                team1: betting_match.team1,
+                team2: betting_match.team2,
+                market: betting_match.market,
+                commission_bps: betting_match.commission_bps,
+                bet_limits: betting_match.bet_limits,
+                status: betting_match.status,
+                phase,
+                result: betting_match.result,
+                totals: betting_match.totals,
+                bets_count: betting_match.bets_count,
+                odds: betting_match.odds,
+                deposit: betting_match.deposit,
+                pot: betting_match.pot,
+            })
+        }
+        /// Returns the status of a match.
+        #[ink(message)]
+        pub fn match_status(&self, match_id: MatchId) -> Option<MatchStatus> {
+            self.matches.get(match_id).map(|betting_match| betting_match.status)
+        }
+        /// Returns the result of a match, `None` if the match does not exist or has no result yet.
+        #[ink(message)]
+        pub fn match_result(&self, match_id: MatchId) -> Option<Outcome> {
+            self.matches.get(match_id).and_then(|betting_match| betting_match.result)
+        }
+        /// Returns the metadata of a match.
+        #[ink(message)]
+        pub fn get_metadata(&self, match_id: MatchId) -> Option<MatchMetadata> {
+            if !self.matches.contains(match_id) {
+                return None;
+            }
+            Some(self.metadata.get(match_id).unwrap_or_default())
+        }
+        /// Returns a bet placed on a match.
+        #[ink(message)]
+        pub fn get_bet(&self, match_id: MatchId, bet_id: BetId) -> Option<Bet> {
+            self.bets.get((match_id, bet_id))
+        }
+        /// Returns the amount bet on each result of a match.
+        #[ink(message)]
+        pub fn pool_totals(&self, match_id: MatchId) -> Option<Vec<Balance>> {
+            self.matches.get(match_id).map(|betting_match| betting_match.totals)
+        }
+
+        /// Returns the current odds of each result of a match, in basis points.
+        /// In a parimutuel match it is what the pot pays per stake after the fee and the commission,
+        /// zero for the results nobody bet on.
+        #[ink(message)]
+        pub fn implied_odds(&self, match_id: MatchId) -> Result<Vec<u32>, Error> {
+            let betting_match = self.matches.get(match_id).ok_or(Error::MatchDoesNotExist)?;
+            if let Some(odds) = betting_match.odds {
+                return Ok(odds);
+            }
+            let pot: Balance = betting_match.totals.iter().sum();
+            let (fee, commission) = self.pot_cuts(&betting_match, pot)?;
+            betting_match.totals.iter()
+                .map(|total| match mul_div(pot - fee - commission, BPS_DENOMINATOR, *total) {
+                    Some(odds) => Ok(u32::try_from(odds).unwrap_or(u32::MAX)),
+                    None if *total == 0 => Ok(0),
+                    None => Err(Error::Overflow),
+                })
+                .collect()
+        }
+
+        /// Returns what a new bet of `amount` on `result` would pay if the match were settled now,
+        /// after the fee and the commission.
+        #[ink(message)]
+        pub fn quote_payout(&self, match_id: MatchId, result: Outcome, amount: Balance) -> Result<Balance, Error> {
+            let betting_match = self.matches.get(match_id).ok_or(Error::MatchDoesNotExist)?;
+            Self::ensure_outcome(&betting_match, result)?;
+            if let Some(odds) = betting_match.odds {
+                return mul_div(amount, odds[result as usize].into(), BPS_DENOMINATOR).ok_or(Error::Overflow);
+            }
+            let pot = betting_match.totals.iter()
+                .try_fold(amount, |pot, total| pot.checked_add(*total))
+                .ok_or(Error::Overflow)?;
+            let winning_total = betting_match.totals[result as usize] + amount;
+            let (fee, commission) = self.pot_cuts(&betting_match, pot)?;
+            Ok(Self::calculate_payout(amount, pot - fee - commission, winning_total).unwrap_or(0))
+        }
+
+        /// Returns the ids of the bets placed by `bettor` on a match.
+        #[ink(message)]
+        pub fn bets_of(&self, match_id: MatchId, bettor: AccountId) -> Vec<BetId> {
+            self.bettors.get((match_id, bettor)).unwrap_or_default()
+        }
+        /// Returns up to `limit` bets of `account`, from the position `from`, with what each one
+        /// can claim. The order changes when a bet is cancelled.
+        /// The claimable amount is computed for each bet alone, claiming several winning bets of a
+        /// match at once can round up to one unit more.
+        #[ink(message)]
+        pub fn positions_of(&self, account: AccountId, from: u32, limit: u32) -> Vec<Position> {
+            let count = self.positions_count.get(account).unwrap_or(0);
+            let until = count.min(from.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            (from..until)
+                .filter_map(|position| self.positions.get((account, position)))
+                .filter_map(|(match_id, bet_id)| {
+                    let betting_match = self.matches.get(match_id)?;
+                    let bet = self.bets.get((match_id, bet_id))?;
+                    Some(Self::position(match_id, bet_id, &betting_match, &bet))
+                })
+                .collect()
+        }
+
+        /// Returns up to `limit` ids of the matches created by `account`, from the position `from`
+        /// in creation order.
+        #[ink(message)]
+        pub fn matches_by_creator(&self, account: AccountId, from: u32, limit: u32) -> Vec<MatchId> {
+            let count = self.creator_matches_count.get(account).unwrap_or(0);
+            let until = count.min(from.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            (from..until).filter_map(|position| self.creator_matches.get((account, position))).collect()
+        }
+
+        /// Returns the ids of the matches in `phase` among `limit` positions of the index, from the
+        /// position `from`, and the position to read the next page from, `None` at the end of the index.
+        /// The open, in play and awaiting result matches share an index, so a page can return less
+        /// than `limit` matches, even none, before the end of the index.
+        /// The order of the index changes when a match is settled or cancelled.
+        #[ink(message)]
+        pub fn list_matches(&self, phase: MatchPhase, from: u32, limit: u32) -> (Vec<MatchId>, Option<u32>) {
+            let index = match phase {
+                MatchPhase::Settled => MatchIndex::Settled,
+                MatchPhase::Cancelled => MatchIndex::Cancelled,
+                _ => MatchIndex::Active,
+            };
+            let now = self.env().block_number();
+            let len = self.index_len.get(index).unwrap_or(0);
+            let until = len.min(from.saturating_add(limit.min(MAX_PAGE_SIZE)));
+            let match_ids = (from..until)
+                .filter_map(|position| self.index.get((index, position)))
+                .filter(|match_id| index != MatchIndex::Active || self.matches.get(match_id)
+                    .is_some_and(|betting_match| Self::phase_of(&betting_match, now) == phase))
+                .collect();
+            let next = if until < len { Some(until) } else { None };
+            (match_ids, next)
+        }
+
+        /// Checks the parameters of a new match and builds it with the transferred deposit.
+        fn new_match(
+            &self,
+            team1: Vec<u8>,
+            team2: Vec<u8>,
+            start: BlockNumber,
+            length: BlockNumber,
+            market: Market,
+            bet_limits: BetLimits,
+        ) -> Result<Match, Error> {
+            self.ensure_not_paused()?;
+            // Check if start and length are valid
+            let current_block_number = self.env().block_number();
+            if current_block_number > (start + length) {
+                return Err(Error::TimeMatchOver)
+            }
+            if bet_limits.min_stake > bet_limits.max_stake || bet_limits.max_bets == 0 {
+                return Err(Error::InvalidBetLimits)
+            }
+            let team1 = TeamName::try_from(team1)?;
+            let team2 = TeamName::try_from(team2)?;
+            if team1 == team2 {
+                return Err(Error::SameTeams)
+            }
+            let outcomes = market.outcomes();
+            if !(2..=MAX_OUTCOMES).contains(&outcomes) || market.has_push() {
+                return Err(Error::InvalidMarket)
+            }
+            // The outcomes need valid and different names
+            if let Market::Outcomes(names) = &market {
+                for (i, name) in names.iter().enumerate() {
+                    name.validate()?;
+                    if names[..i].contains(name) {
+                        return Err(Error::InvalidMarket)
+                    }
+                }
+            }
+            // Check the deposit.
+            let deposit = Self::env().transferred_value();
+            if deposit < self.min_deposit {
+                return Err(Error::NotEnoughDeposit)
+            }
+            Ok(Match {
+                creator: Self::env().caller(),
+                start,
+                length,
+                team1,
+                team2,
+                result: None,
+                market,
+                commission_bps: 0,
+                bet_limits,
+                odds: None,
+                payouts: vec![0; outcomes as usize],
+                challenge_end: 0,
+                bets_count: 0,
+                totals: vec![0; outcomes as usize],
+                deposit,
+                status: MatchStatus::Open,
+                frozen: false,
+                pot: 0,
+                refunds: false,
+                claimed_stake: 0,
+                paid_out: 0,
+            })
+        }
+
+        /// Stores a new match and its metadata, assigning it the next match id.
+        fn insert_match(&mut self, betting_match: Match, metadata: MatchMetadata) -> Result<MatchId, Error> {
+            let creator = betting_match.creator;
+            if let Some(competition) = &metadata.competition {
+                competition.validate()?;
+            }
+            // Hash the match object.
+            let match_hash = Self::get_match_hash(&betting_match, metadata.fixture_id);
+            // Check if match already exists by checking its specs hash.
+            if self.matches_hashes.contains(match_hash) {
+                return Err(Error::MatchAlreadyExists)
+            }
+            // Assign the next match id.
+            let match_id = self.next_match_id;
+            self.next_match_id += 1;
+            // Store the match hash with its match id.
+            self.matches_hashes.insert(match_hash, &match_id);
+            // Store the betting match in the list of open matches
+            self.matches.insert(match_id, &betting_match);
+            // Only store the metadata if there is any.
+            if metadata != MatchMetadata::default() {
+                self.metadata.insert(match_id, &metadata);
+            }
+            // Add the match to the matches of its creator and to the active ones.
+            self.add_creator_match(creator, match_id);
+            self.push_index(MatchIndex::Active, match_id);
+            // Emit an event.
+            self.env().emit_event(MatchCreated {
+                match_id,
+                who: creator,
+                team1: betting_match.team1,
+                team2: betting_match.team2,
+                start: betting_match.start,
+                length: betting_match.length,
+                commission_bps: betting_match.commission_bps,
+                odds: betting_match.odds,
+                metadata,
+            });
+
+            Ok(match_id)
+        }
+
+        /// Returns a bet of the caller and its match, if the bet can still be changed.
+        fn bet_to_change(&self, match_id: MatchId, bet_id: BetId) -> Result<(Match, Bet), Error> {
+            self.ensure_not_paused()?;
+            let betting_match = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            Self::ensure_not_frozen(&betting_match)?;
+            Self::ensure_open(&betting_match)?;
+            if self.env().block_number() > betting_match.start {
+                return Err(Error::MatchHasStarted)
+            }
+            let bet = match self.bets.get((match_id, bet_id)) {
+                Some(bet_from_storage) => bet_from_storage,
+                None => return Err(Error::BetDoesNotExist)
+            };
+            if bet.bettor != Self::env().caller() {
+                return Err(Error::NotBettor);
+            }
+            Ok((betting_match, bet))
+        }
+
+        /// Records the result submitted by a reporter of the quorum of a match. The result is set
+        /// when `threshold` reporters agree, and the match disputed when they can not agree anymore.
+        fn submit_result(
+            &mut self,
+            match_id: MatchId,
+            mut betting_match: Match,
+            mut quorum: Quorum,
+            reporter: AccountId,
+            result: Outcome,
+        ) -> Result<(), Error> {
+            if betting_match.result.is_some() {
+                return Err(Error::ResultAlreadySet);
+            }
+            if self.submissions.contains((match_id, reporter)) {
+                return Err(Error::AlreadySubmitted);
+            }
+            self.submissions.insert((match_id, reporter), &result);
+            quorum.submitted += 1;
+            quorum.votes[result as usize] += 1;
+            self.env().emit_event(ResultSubmitted {
+                match_id,
+                reporter,
+                result,
+            });
+            if quorum.votes[result as usize] >= quorum.threshold {
+                betting_match.result = Some(result);
+                betting_match.challenge_end = self.env().block_number() + self.challenge_period;
+                self.env().emit_event(MatchResultSet {
+                    match_id,
+                    result
+                });
+            } else {
+                // Check if the remaining reporters can still reach the threshold
+                let remaining = quorum.reporters.len() as u32 - quorum.submitted;
+                let most_votes = quorum.votes.iter().max().copied().unwrap_or_default();
+                if most_votes + remaining < quorum.threshold {
+                    betting_match.status = MatchStatus::Disputed;
+                    self.env().emit_event(MatchDisputed { match_id });
+                }
+            }
+            self.quorums.insert(match_id, &quorum);
+            self.matches.insert(match_id, &betting_match);
+            Ok(())
+        }
+
+        /// Converts a match and its bets from the version 1 layout, adding them to the indexes
+        /// that did not exist in it. A match of the version 1 has the `MatchResult` market.
+        fn migrate_match(&mut self, match_id: MatchId, legacy_match: MatchV1) -> Result<(), Error> {
+            let bets_key = self.bets.key();
+            let legacy_hash = Self::legacy_match_hash(&legacy_match);
+            let betting_match = Match {
+                creator: legacy_match.creator,
+                start: legacy_match.start,
+                length: legacy_match.length,
+                team1: TeamName(legacy_match.team1),
+                team2: TeamName(legacy_match.team2),
+                result: legacy_match.result.map(Into::into),
+                market: Market::MatchResult,
+                commission_bps: 0,
+                bet_limits: Default::default(),
+                odds: None,
+                payouts: vec![0; 3],
+                challenge_end: legacy_match.challenge_end,
+                bets_count: legacy_match.bets_count,
+                totals: legacy_match.totals,
+                deposit: legacy_match.deposit,
+                status: legacy_match.status,
+                frozen: legacy_match.frozen,
+                pot: legacy_match.pot,
+                refunds: legacy_match.refunds,
+                claimed_stake: legacy_match.claimed_stake,
+                paid_out: legacy_match.paid_out,
+            };
+            for bet_id in 0..legacy_match.bets_count {
+                let legacy_bet = ink::env::get_contract_storage::<_, BetV1>(&(&bets_key, (match_id, bet_id)))
+                    .unwrap_or_default()
+                    .ok_or(Error::BetDoesNotExist)?;
+                self.bets.insert((match_id, bet_id), &Bet {
+                    bettor: legacy_bet.bettor,
+                    amount: legacy_bet.amount,
+                    result: legacy_bet.result.into(),
+                    odds: None,
+                    claimed: legacy_bet.claimed,
+                });
+                // An account could only place one bet on a match in the version 1
+                self.bettors.insert((match_id, legacy_bet.bettor), &vec![bet_id]);
+                self.add_position(legacy_bet.bettor, match_id, bet_id);
+            }
+            // The hash of the match now includes its market
+            self.matches_hashes.remove(legacy_hash);
+            let index = match betting_match.status {
+                MatchStatus::Settled => MatchIndex::Settled,
+                MatchStatus::Cancelled => MatchIndex::Cancelled,
+                MatchStatus::Open | MatchStatus::Disputed => {
+                    self.matches_hashes.insert(Self::get_match_hash(&betting_match, None), &match_id);
+                    MatchIndex::Active
+                }
+            };
+            self.add_creator_match(betting_match.creator, match_id);
+            self.push_index(index, match_id);
+            self.matches.insert(match_id, &betting_match);
+            Ok(())
+        }
+
+        /// Hash of a match in the version 1 layout.
+        fn legacy_match_hash(legacy_match: &MatchV1) -> [u8;32] {
+            let entropy = (
+                &legacy_match.team1,
+                &legacy_match.team2,
+                legacy_match.start,
+                legacy_match.length,
+            );
+            let mut message =
+                <ink::env::hash::Sha2x256 as ink::env::hash::HashOutput>::Type::default();
+            ink::env::hash_encoded::<ink::env::hash::Sha2x256, _>(
+                &entropy,
+                &mut message,
+            );
+            message
+        }
+
+        /// Phase of a match at the block `now`.
+        fn phase_of(betting_match: &Match, now: BlockNumber) -> MatchPhase {
+            match betting_match.status {
+                MatchStatus::Settled => MatchPhase::Settled,
+                MatchStatus::Cancelled => MatchPhase::Cancelled,
+                MatchStatus::Disputed => MatchPhase::AwaitingResult,
+                MatchStatus::Open if now <= betting_match.start => MatchPhase::Open,
+                MatchStatus::Open if now <= betting_match.start + betting_match.length => MatchPhase::InPlay,
+                MatchStatus::Open => MatchPhase::AwaitingResult,
+            }
+        }
+
+        /// State of a bet and the amount its bettor can claim.
+        fn position(match_id: MatchId, bet_id: BetId, betting_match: &Match, bet: &Bet) -> Position {
+            let won = betting_match.result == Some(bet.result);
+            let (status, claimable) = match betting_match.status {
+                MatchStatus::Open | MatchStatus::Disputed => (PositionStatus::Pending, 0),
+                _ if bet.claimed => (PositionStatus::Claimed, 0),
+                _ if betting_match.refunds => (PositionStatus::Refundable, bet.amount),
+                _ if !won => (PositionStatus::Lost, 0),
+                _ => {
+                    let total_winners = betting_match.totals[bet.result as usize];
+                    let winnings = if betting_match.odds.is_some() {
+                        Self::fixed_payout(bet).unwrap_or_default()
+                    } else {
+                        Self::calculate_payout(bet.amount, betting_match.pot, total_winners).unwrap_or_default()
+                    };
+                    (PositionStatus::Won, winnings)
+                }
+            };
+            Position {
+                match_id,
+                bet_id,
+                amount: bet.amount,
+                result: bet.result,
+                status,
+                claimable,
+            }
+        }
+
+        /// Adds a bet to the bets of its account.
+        fn add_position(&mut self, account: AccountId, match_id: MatchId, bet_id: BetId) {
+            let count = self.positions_count.get(account).unwrap_or(0);
+            self.positions.insert((account, count), &(match_id, bet_id));
+            self.position_of.insert((match_id, bet_id), &count);
+            self.positions_count.insert(account, &(count + 1));
+        }
+
+        /// Removes a bet from the bets of its account, its position is taken by the last bet.
+        fn remove_position(&mut self, account: AccountId, match_id: MatchId, bet_id: BetId) {
+            if let Some(position) = self.position_of.take((match_id, bet_id)) {
+                let last = self.positions_count.get(account).unwrap_or(0).saturating_sub(1);
+                if let Some(last_bet) = self.positions.take((account, last)) {
+                    if position != last {
+                        self.positions.insert((account, position), &last_bet);
+                        self.position_of.insert(last_bet, &position);
+                    }
+                }
+                self.positions_count.insert(account, &last);
+            }
+        }
+
+        /// Adds a match to the matches of its creator.
+        fn add_creator_match(&mut self, creator: AccountId, match_id: MatchId) {
+            let count = self.creator_matches_count.get(creator).unwrap_or(0);
+            self.creator_matches.insert((creator, count), &match_id);
+            self.creator_matches_count.insert(creator, &(count + 1));
+        }
+
+        /// Adds a match to the end of an index.
+        fn push_index(&mut self, index: MatchIndex, match_id: MatchId) {
+            let len = self.index_len.get(index).unwrap_or(0);
+            self.index.insert((index, len), &match_id);
+            self.index_position.insert(match_id, &len);
+            self.index_len.insert(index, &(len + 1));
+        }
+
+        /// Moves a match between indexes, its position is taken by the last match of the index.
+        fn move_index(&mut self, match_id: MatchId, from: MatchIndex, to: MatchIndex) {
+            if let Some(position) = self.index_position.get(match_id) {
+                let last = self.index_len.get(from).unwrap_or(0).saturating_sub(1);
+                if let Some(last_match) = self.index.take((from, last)) {
+                    if position != last {
+                        self.index.insert((from, position), &last_match);
+                        self.index_position.insert(last_match, &position);
+                    }
+                }
+                self.index_len.insert(from, &last);
+            }
+            self.push_index(to, match_id);
+        }
+
+        fn set_frozen(&mut self, match_id: MatchId, frozen: bool) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let mut betting_match = match self.matches.get(match_id) {
+                Some(match_from_storage) => match_from_storage,
+                None => return Err(Error::MatchDoesNotExist)
+            };
+            betting_match.frozen = frozen;
+            self.matches.insert(match_id, &betting_match);
+            Ok(())
+        }
+
+        /// Checks the Smart Contract is not paused.
+        fn ensure_not_paused(&self) -> Result<(), Error> {
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        /// Checks the match is not frozen.
+        fn ensure_not_frozen(betting_match: &Match) -> Result<(), Error> {
+            if betting_match.frozen {
+                return Err(Error::MatchFrozen);
+            }
+            Ok(())
+        }
+
+        /// Checks the caller is the owner.
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.owner != Some(Self::env().caller()) {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Keeps funds of the house in the treasury, the admins withdraw them with `withdraw_fees`.
+        fn pay_house(&mut self, amount: Balance) {
+            self.treasury += amount;
+        }
+
+        /// Checks the caller is an admin.
+        fn ensure_admin(&self) -> Result<(), Error> {
+            if !self.has_role(Role::Admin, Self::env().caller()) {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+
+        /// Checks the caller can grant and revoke a role.
+        fn ensure_can_manage(&self, role: Role) -> Result<(), Error> {
+            match role {
+                Role::Admin => self.ensure_owner(),
+                _ => self.ensure_admin(),
+            }
+        }
+
+        /// Checks the match still accepts changes.
+        fn ensure_open(betting_match: &Match) -> Result<(), Error> {
+            match betting_match.status {
+                MatchStatus::Open => Ok(()),
+                MatchStatus::Disputed => Err(Error::MatchDisputed),
+                MatchStatus::Settled => Err(Error::MatchAlreadySettled),
+                MatchStatus::Cancelled => Err(Error::MatchCancelled),
+            }
+        }
+
+        /// Protocol fee and commission of the creator taken from the `pot` of a parimutuel match with winners.
+        fn pot_cuts(&self, betting_match: &Match, pot: Balance) -> Result<(Balance, Balance), Error> {
+            let fee = mul_div(pot, self.fee_bps.into(), BPS_DENOMINATOR).ok_or(Error::Overflow)?;
+            let commission = mul_div(pot, betting_match.commission_bps.into(), BPS_DENOMINATOR)
+                .ok_or(Error::Overflow)?;
+            Ok((fee, commission))
+        }
+
+        /// Checks there are odds above 1 for every outcome of the market.
+        fn ensure_valid_odds(odds: &[u32], market: &Market) -> Result<(), Error> {
+            if odds.len() != market.outcomes() as usize || odds.iter().any(|odds| Balance::from(*odds) <= BPS_DENOMINATOR) {
+                return Err(Error::InvalidOdds);
+            }
+            Ok(())
+        }
+
+        /// Checks the outcome exists in the market of the match.
+        fn ensure_outcome(betting_match: &Match, outcome: Outcome) -> Result<(), Error> {
+            if outcome >= betting_match.market.outcomes() {
+                return Err(Error::InvalidOutcome);
+            }
+            Ok(())
+        }
+
+        /// Checks the deposit and the stakes of the match cover the payouts of any result.
+        fn ensure_collateral(betting_match: &Match) -> Result<(), Error> {
+            let collateral = betting_match.totals.iter()
+                .try_fold(betting_match.deposit, |collateral, total| collateral.checked_add(*total))
+                .ok_or(Error::Overflow)?;
+            if betting_match.payouts.iter().any(|payout| *payout > collateral) {
+                return Err(Error::InsufficientCollateral);
+            }
+            Ok(())
+        }
+
+        /// Payout of a bet at its locked odds, zero if the match has not fixed odds.
+        fn fixed_payout(bet: &Bet) -> Result<Balance, Error> {
+            match bet.odds {
+                Some(odds) => mul_div(bet.amount, odds.into(), BPS_DENOMINATOR).ok_or(Error::Overflow),
+                None => Ok(0),
+            }
+        }
+
+        /// Share of the `pot` won by a winning bet of `amount`, where `winning_total` is the
+        /// sum of all the winning bets: `amount * pot / winning_total` rounded down.
+        pub fn calculate_payout(
+            amount: Balance,
+            pot: Balance,
+            winning_total: Balance,
+        ) -> Option<Balance> {
+            mul_div(amount, pot, winning_total)
+        }
+
+        /// Hash of a stored match, with the fixture id of its metadata.
+        fn match_hash(&self, match_id: MatchId, betting_match: &Match) -> [u8;32] {
+            let fixture_id = self.metadata.get(match_id).and_then(|metadata| metadata.fixture_id);
+            Self::get_match_hash(betting_match, fixture_id)
+        }
+
+        pub fn get_match_hash(
+            betting_match: &Match,
+            fixture_id: Option<u64>,
+        ) -> [u8;32] {
+            let entropy = (
+                &betting_match.team1,
+                &betting_match.team2,
+                betting_match.start,
+                betting_match.length,
+                &betting_match.market,
+                fixture_id,
+            );
+            let mut message =
+                <ink::env::hash::Sha2x256 as ink::env::hash::HashOutput>::Type::default();
+            ink::env::hash_encoded::<ink::env::hash::Sha2x256, _>(
+                &entropy,
+                &mut message,
+            );
+            message
+        }
+    }
+
+    #[cfg(test)]
+    impl Betting {
+        /// Stores a match and its bets in the version 1 layout, as a Smart Contract deployed with it would.
+        pub fn insert_v1_match(&mut self, legacy_match: &MatchV1, legacy_bets: &[BetV1]) -> MatchId {
+            let match_id = self.next_match_id;
+            self.next_match_id += 1;
+            ink::env::set_contract_storage(&(&self.matches.key(), match_id), legacy_match);
+            for (bet_id, legacy_bet) in legacy_bets.iter().enumerate() {
+                let bet_id = bet_id as BetId;
+                ink::env::set_contract_storage(&(&self.bets.key(), (match_id, bet_id)), legacy_bet);
+                ink::env::set_contract_storage(&(&self.bettors.key(), (match_id, legacy_bet.bettor)), &bet_id);
+            }
+            self.matches_hashes.insert(Self::legacy_match_hash(legacy_match), &match_id);
+            self.version = 1;
+            match_id
+        }
+    }
+
+    /// Computes `a * b / c` rounded down using a 256 bits intermediate product.
+    /// Returns `None` if `c` is zero or the result does not fit in 128 bits.
+    fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+        if c == 0 {
+            return None;
+        }
+        // Multiply the 64 bits halves to get the high and low 128 bits of the product
+        const MASK: u128 = u64::MAX as u128;
+        let (a_hi, a_lo) = (a >> 64, a & MASK);
+        let (b_hi, b_lo) = (b >> 64, b & MASK);
+        let lo_lo = a_lo * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_lo = a_hi * b_lo;
+        let mid = (lo_lo >> 64) + (lo_hi & MASK) + (hi_lo & MASK);
+        let lo = (lo_lo & MASK) | (mid << 64);
+        let hi = a_hi * b_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64);
+        if hi == 0 {
+            return Some(lo / c);
+        }
+        if hi >= c {
+            return None;
+        }
+        // Long division of the 256 bits product, the quotient fits in 128 bits
+        let mut remainder = hi;
+        let mut quotient = 0u128;
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((lo >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= c {
+                remainder = remainder.wrapping_sub(c);
+                quotient |= 1;
+            }
         }
+        Some(quotient)
     }
 }
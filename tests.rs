@@ -3,7 +3,7 @@
 /// The below code is technically just normal Rust code.
 #[cfg(test)]
 mod tests {
-    use crate::{betting::{Betting, Error, MatchResult, Bet}};
+    use crate::{betting::{Betting, Error, MatchResult, Bet, MatchId, NoWinnersPolicy, MatchStatus, CancelReason, Role, MatchV1, BetV1, BetLimits, Market, Outcome, TeamName, MAX_TEAM_NAME_LEN, MatchMetadata, Sport, MatchPhase, Position, PositionStatus, MatchView, MATCH_VIEW_VERSION}};
     use ink::primitives::AccountId;
 
     fn set_accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
@@ -29,18 +29,48 @@ mod tests {
     fn create_contract(who: AccountId) -> Betting {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
-        let betting = Betting::new();
+        let betting = Betting::new(0).unwrap();
         betting
     }
 
 
-    fn create_match(betting: &mut Betting, who: AccountId, t1: &str, t2: &str, start: u32, length: u32, deposit: u128) -> AccountId {
+    fn create_match(betting: &mut Betting, who: AccountId, t1: &str, t2: &str, start: u32, length: u32, deposit: u128) -> MatchId {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(deposit);
         // ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(deposit);
         // Dispatch a signed extrinsic.
-        assert_eq!(betting.create_match_to_bet(t1.as_bytes().to_vec(), t2.as_bytes().to_vec(), start, length), Ok(()));
-        who
+        let result = betting.create_match_to_bet(t1.as_bytes().to_vec(), t2.as_bytes().to_vec(), start, length, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default());
+        assert!(result.is_ok());
+        result.unwrap()
+    }
+
+    fn place_bet(betting: &mut Betting, who: AccountId, match_id: MatchId, amount: u128, result: Outcome) {
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(amount);
+        assert_eq!(betting.bet(match_id, result), Ok(()));
+    }
+
+    fn place_bet_fails(betting: &mut Betting, who: AccountId, match_id: MatchId, error: Error) {
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000);
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Err(error));
+    }
+
+    /// Django creates a match where Bob and Eve bet on team1 and Charlie on team2,
+    /// then Alice sets team1 as the winner.
+    fn create_finished_match(betting: &mut Betting) -> MatchId {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let match_id = create_match(betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(betting, accounts.charlie, match_id, 10000000000000, MatchResult::Team2Victory.into());
+        place_bet(betting, accounts.eve, match_id, 30000000000000, MatchResult::Team1Victory.into());
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        match_id
     }
 
     /// We test if the default constructor does its job.
@@ -48,7 +78,7 @@ mod tests {
     fn constructor_works() {
         let accounts = set_accounts();
         let betting = create_contract(accounts.alice);
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
     }
 
     #[ink::test]
@@ -56,11 +86,12 @@ mod tests {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
 
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
 
         let match_id = create_match(&mut betting, accounts.alice, "team1", "team2", 10, 10, 1000000000000);
 
         assert_eq!(betting.exists_match(match_id), true);
+        assert_eq!(betting.matches_by_creator(accounts.alice, 0, 10), vec![match_id]);
 
         let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
         assert_eq!(1, emitted_events.len());
@@ -72,34 +103,32 @@ mod tests {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
         
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1);
 
         assert_eq!(
-            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10),
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
             Err(Error::NotEnoughDeposit)
         );
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
     }
 
     #[ink::test]
-    fn match_exist_when_create_match_to_bet() {
+    fn create_multiple_matches_with_same_creator() {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
         
-        assert_eq!(betting.exists_match(accounts.alice), false);
-
-        create_match(&mut betting, accounts.alice, "team1", "team2", 10, 10, 1000000000000);
+        assert_eq!(betting.exists_match(0), false);
 
-        assert_eq!(betting.exists_match(accounts.alice), true);
+        let first_match = create_match(&mut betting, accounts.alice, "team1", "team2", 10, 10, 1000000000000);
+        let second_match = create_match(&mut betting, accounts.alice, "team3", "team4", 10, 10, 1000000000000);
 
-        //Try to added it again
-        assert_eq!(
-            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10),
-            Err(Error::OriginHasAlreadyOpenMatch)
-        );
+        assert_ne!(first_match, second_match);
+        assert_eq!(betting.exists_match(first_match), true);
+        assert_eq!(betting.exists_match(second_match), true);
+        assert_eq!(betting.matches_by_creator(accounts.alice, 0, 10), vec![first_match, second_match]);
     }
 
     #[ink::test]
@@ -112,32 +141,32 @@ mod tests {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
         
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
 
         assert_eq!(
-            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1),
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
             Err(Error::TimeMatchOver)
         );
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
     }
     #[ink::test]
     fn error_creating_two_equal_matches() {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
         
-        assert_eq!(betting.exists_match(accounts.alice), false);
+        assert_eq!(betting.exists_match(0), false);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
-        assert_eq!(betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1), Ok(()));
+        assert_eq!(betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()), Ok(0));
 
         //Bob to create same match as alice just created
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
 
         assert_eq!(
-            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1),
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
             Err(Error::MatchAlreadyExists)
         );
     }
@@ -151,14 +180,19 @@ mod tests {
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
         let bet = Bet {
             bettor: accounts.bob,
             amount: 10000000000,
-            result: MatchResult::Team1Victory,
+            result: MatchResult::Team1Victory.into(),
+            odds: None,
+            claimed: false,
         };
-        assert_eq!(betting.get_match(match_id).unwrap().bets.contains(&bet), true);
+        assert_eq!(betting.get_bet(match_id, 0), Some(bet));
+        let betting_match = betting.get_match(match_id).unwrap();
+        assert_eq!(betting_match.bets_count, 1);
+        assert_eq!(betting_match.totals, vec![10000000000, 0, 0]);
 
         let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
         assert_eq!(2, emitted_events.len());
@@ -172,7 +206,7 @@ mod tests {
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000);
-        assert_eq!(betting.bet(accounts.alice, MatchResult::Team1Victory),  Err(Error::MatchDoesNotExist));
+        assert_eq!(betting.bet(0, MatchResult::Team1Victory.into()),  Err(Error::MatchDoesNotExist));
     }
 
     #[ink::test]
@@ -180,14 +214,14 @@ mod tests {
         let accounts = set_accounts();
         let mut betting = create_contract(accounts.alice);
 
-        create_match(&mut betting, accounts.alice, "team1", "team2", 1, 10, 1000000000000);
+        let match_id = create_match(&mut betting, accounts.alice, "team1", "team2", 1, 10, 1000000000000);
         // Advance 2 blocks
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000);
-        assert_eq!(betting.bet(accounts.alice, MatchResult::Team1Victory),  Err(Error::MatchHasStarted));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()),  Err(Error::MatchHasStarted));
     }
 
     #[ink::test]
@@ -199,9 +233,10 @@ mod tests {
 
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory),  Err(Error::AlreadyBet));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()),  Err(Error::AlreadyBet));
+        assert_eq!(betting.bet(match_id, MatchResult::Draw.into()),  Err(Error::AlreadyBet));
 
     }
 
@@ -218,7 +253,7 @@ mod tests {
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
-        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
         let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
         assert_eq!(2, emitted_events.len());
@@ -237,7 +272,7 @@ mod tests {
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         //set Bob as the caller
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory), Err(Error::BadOrigin));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Err(Error::NotReporter));
     }
     #[ink::test]
     fn set_result_match_not_exist() {
@@ -248,7 +283,7 @@ mod tests {
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
-        assert_eq!(betting.set_result(accounts.alice, MatchResult::Team1Victory), Err(Error::MatchDoesNotExist));
+        assert_eq!(betting.set_result(0, MatchResult::Team1Victory.into()), Err(Error::MatchDoesNotExist));
     }
     #[ink::test]
     fn set_result_match_not_finished() {
@@ -256,7 +291,7 @@ mod tests {
         let mut betting = create_contract(accounts.alice);
         let match_id = create_match(&mut betting, accounts.alice, "team1", "team2", 10, 10, 1000000000000);
 
-        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory), Err(Error::TimeMatchNotOver));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Err(Error::TimeMatchNotOver));
     }
 
     #[ink::test]
@@ -271,15 +306,15 @@ mod tests {
         // Bob bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
         // Charlie bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory.into()), Ok(()));
         // Eve bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(30000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
         // Advance 3 blocks
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
@@ -287,10 +322,16 @@ mod tests {
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         //Alice set the result
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
         //Django distributes the winnings
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
-        assert_eq!(betting.distribute_winnings(), Ok(()));
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Settled);
+        //the winners claim their share
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
         //bob has 90 + 12.5 (winner)
         assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(102500000000000));
         //charlie has 90 (loser)
@@ -312,15 +353,15 @@ mod tests {
         // Bob bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
         // Charlie bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory.into()), Ok(()));
         // Eve bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(30000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
         // Advance 3 blocks
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
@@ -328,10 +369,12 @@ mod tests {
         ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
         //Alice set the result
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
-        //alice distribute winner doesn't exists
-        assert_eq!(betting.distribute_winnings(), Err(Error::MatchDoesNotExist));
+        //the match to distribute doesn't exists
+        assert_eq!(betting.distribute_winnings(match_id + 1), Err(Error::MatchDoesNotExist));
+        //alice is not the creator of the match
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::NotMatchCreator));
 
     }
 
@@ -347,19 +390,1706 @@ mod tests {
         // Bob bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
         // Charlie bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team2Victory.into()), Ok(()));
         // Eve bets
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
         ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(30000000000000);
-        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory), Ok(()));
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Ok(()));
 
         //Django distributes the winnings
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
-        assert_eq!(betting.distribute_winnings(), Err(Error::MatchNotResult));
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::MatchNotResult));
+
+    }
+
+    #[ink::test]
+    fn distribute_winnings_already_settled() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::MatchAlreadySettled));
+        //the result can not be changed either
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team2Victory.into()), Err(Error::MatchAlreadySettled));
+    }
+
+    #[ink::test]
+    fn claim_winnings_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        //eve has 70 + 37.5 (winner)
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(107500000000000));
+        assert!(betting.get_bet(match_id, 2).unwrap().claimed);
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 1 match created + 3 bets + result set + match settled + winnings claimed
+        assert_eq!(7, emitted_events.len());
+    }
+
+    #[ink::test]
+    fn claim_winnings_twice() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::AlreadyClaimed));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(102500000000000));
+    }
+
+    #[ink::test]
+    fn claim_winnings_errors() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id + 1), Err(Error::MatchDoesNotExist));
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::MatchNotSettled));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        //charlie lost
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::NotWinningBet));
+        //django did not bet
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::BetDoesNotExist));
+    }
+
+    #[ink::test]
+    fn calculate_payout_is_exact() {
+        // 10 of the 40 winning units take a quarter of a 50 units pot, rounding down
+        assert_eq!(Betting::calculate_payout(10, 50, 40), Some(12));
+        assert_eq!(Betting::calculate_payout(30, 50, 40), Some(37));
+        // Stakes below 100 do not lose precision nor divide by zero
+        assert_eq!(Betting::calculate_payout(1, 3, 2), Some(1));
+        assert_eq!(Betting::calculate_payout(5, 5, 5), Some(5));
+        assert_eq!(Betting::calculate_payout(1, 1, 0), None);
+    }
+
+    #[ink::test]
+    fn calculate_payout_huge_stakes() {
+        assert_eq!(Betting::calculate_payout(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(Betting::calculate_payout(u128::MAX / 2, u128::MAX, u128::MAX / 2 + 1), Some(u128::MAX - 2));
+        assert_eq!(Betting::calculate_payout(u128::MAX / 3, u128::MAX, u128::MAX / 3), Some(u128::MAX));
+        // The payout does not fit in a balance
+        assert_eq!(Betting::calculate_payout(u128::MAX, u128::MAX, 1), None);
+    }
+
+    #[ink::test]
+    fn claim_winnings_tiny_stakes() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        // Accept the dust stakes
+        assert_eq!(betting.set_limits(1000000000000, 0, u128::MAX), Ok(()));
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 1, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 1, MatchResult::Team2Victory.into());
+        place_bet(&mut betting, accounts.eve, match_id, 1, MatchResult::Team1Victory.into());
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        // Each winner gets 3 * 1 / 2 = 1
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(100000000000000));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(100000000000000));
+        // The last winner to claim sends the remaining unit to the creator
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(100000000000000));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(100000000000001));
+    }
+
+    #[ink::test]
+    fn claim_winnings_huge_stakes() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let bob_stake = u128::MAX / 2;
+        let eve_stake = u128::MAX / 8;
+        let charlie_stake = u128::MAX / 4;
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, bob_stake);
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.eve, eve_stake);
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie, charlie_stake);
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, bob_stake, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, charlie_stake, MatchResult::Team2Victory.into());
+        place_bet(&mut betting, accounts.eve, match_id, eve_stake, MatchResult::Team1Victory.into());
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(238197656844656924424362225202237748017));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(59549414211164231106090556300559437003));
+        // The rounding remainder goes to the creator
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(100000000000001));
+    }
+
+    /// Django creates a match where Bob and Charlie bet on team2, then Alice sets a draw.
+    fn create_match_without_winners(betting: &mut Betting) -> MatchId {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        let match_id = create_match(betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(betting, accounts.bob, match_id, 10000000000000, MatchResult::Team2Victory.into());
+        place_bet(betting, accounts.charlie, match_id, 20000000000000, MatchResult::Team2Victory.into());
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Ok(()));
+        match_id
+    }
+
+    #[ink::test]
+    fn no_winners_refunds_the_stakes() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.no_winners_policy(), NoWinnersPolicy::Refund);
+        let match_id = create_match_without_winners(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert!(betting.get_match(match_id).unwrap().refunds);
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 1 match created + 2 bets + result set + match settled + no winners refunded
+        assert_eq!(6, emitted_events.len());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::AlreadyClaimed));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(100000000000000));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie), Ok(100000000000000));
+    }
+
+    #[ink::test]
+    fn no_winners_pot_to_the_house() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_no_winners_policy(NoWinnersPolicy::House), Ok(()));
+        let match_id = create_match_without_winners(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert!(!betting.get_match(match_id).unwrap().refunds);
+        // the 30 of the pot go to the treasury, nothing is left to claim
+        assert_eq!(betting.treasury(), 30000000000000);
+        assert_eq!(betting.get_match(match_id).unwrap().pot, 0);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::NotWinningBet));
+    }
+
+    #[ink::test]
+    fn set_no_winners_policy_bad_origin() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_no_winners_policy(NoWinnersPolicy::House), Err(Error::NotAdmin));
+        assert_eq!(betting.no_winners_policy(), NoWinnersPolicy::Refund);
+    }
+
+    #[ink::test]
+    fn cancel_match_by_creator_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Cancelled);
+        // django gets the deposit back
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(100000000000000));
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 1 match created + 1 bet + match cancelled
+        assert_eq!(3, emitted_events.len());
+
+        // bob gets the stake back
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(100000000000000));
+
+        // the match does not accept changes anymore
+        place_bet_fails(&mut betting, accounts.eve, match_id, Error::MatchCancelled);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Err(Error::MatchCancelled));
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::MatchCancelled));
+    }
+
+    #[ink::test]
+    fn cancel_match_bad_origin() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 10, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Err(Error::NotMatchCreator));
+        // Advance 2 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Abandoned), Err(Error::MatchHasStarted));
+    }
+
+    #[ink::test]
+    fn cancel_match_by_owner_slashes_deposit() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 10, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        // Advance 2 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Invalid), Ok(()));
+        assert_eq!(betting.treasury(), 1000000000000);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(99000000000000));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(100000000000000));
+    }
+
+    #[ink::test]
+    fn cancel_match_already_settled() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Invalid), Err(Error::MatchAlreadySettled));
+    }
+
+    #[ink::test]
+    fn grant_reporter_role_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        let other_match_id = create_match(&mut betting, accounts.django, "team3", "team4", 1, 1, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.grant_role(Role::Reporter, accounts.bob), Ok(()));
+        assert_eq!(betting.grant_role(Role::MatchReporter(match_id), accounts.charlie), Ok(()));
+        assert!(betting.has_role(Role::Reporter, accounts.bob));
+        assert!(betting.has_role(Role::MatchReporter(match_id), accounts.charlie));
+        assert!(!betting.has_role(Role::MatchReporter(other_match_id), accounts.charlie));
+
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        // charlie can only report its match
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.set_result(other_match_id, MatchResult::Draw.into()), Err(Error::NotReporter));
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Ok(()));
+        // bob reports any match
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_result(other_match_id, MatchResult::Draw.into()), Ok(()));
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 2 matches created + 2 roles granted + 2 results set
+        assert_eq!(6, emitted_events.len());
+    }
+
+    #[ink::test]
+    fn revoke_role_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.grant_role(Role::Reporter, accounts.bob), Ok(()));
+        assert_eq!(betting.revoke_role(Role::Reporter, accounts.bob), Ok(()));
+        assert!(!betting.has_role(Role::Reporter, accounts.bob));
+
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Err(Error::NotReporter));
+    }
+
+    #[ink::test]
+    fn manage_roles_bad_origin() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        // bob is not an admin
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.grant_role(Role::Reporter, accounts.bob), Err(Error::NotAdmin));
+        // only the owner manages the admins
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.grant_role(Role::Admin, accounts.bob), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.grant_role(Role::Admin, accounts.charlie), Err(Error::NotOwner));
+        assert_eq!(betting.revoke_role(Role::Admin, accounts.bob), Err(Error::NotOwner));
+        // an admin manages the reporters and the settings
+        assert_eq!(betting.grant_role(Role::Reporter, accounts.charlie), Ok(()));
+        assert_eq!(betting.set_no_winners_policy(NoWinnersPolicy::House), Ok(()));
+    }
+
+    #[ink::test]
+    fn quorum_sets_result_when_threshold_agrees() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob, accounts.charlie, accounts.eve], 2), Ok(()));
+
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        // alice is not in the quorum anymore
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Err(Error::NotReporter));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Err(Error::AlreadySubmitted));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Ok(()));
+        assert_eq!(betting.get_quorum(match_id).unwrap().submitted, 2);
+        // the threshold is reached with the third submission
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        assert_eq!(betting.get_quorum(match_id).unwrap().votes, vec![2, 0, 1]);
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 1 match created + 3 results submitted + result set
+        assert_eq!(5, emitted_events.len());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+    }
+
+    #[ink::test]
+    fn quorum_disagreement_disputes_the_match() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob, accounts.charlie], 2), Ok(()));
 
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team2Victory.into()), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Disputed);
+
+        // the match can not be settled until an admin resolves it
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::MatchDisputed));
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team1Victory.into()), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team1Victory.into()), Err(Error::MatchNotDisputed));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Open);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+    }
+
+    #[ink::test]
+    fn assign_reporters_invalid_quorum() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob], 1), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.assign_reporters(match_id + 1, vec![accounts.bob], 1), Err(Error::MatchDoesNotExist));
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob], 0), Err(Error::InvalidQuorum));
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob], 2), Err(Error::InvalidQuorum));
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob, accounts.bob], 2), Err(Error::InvalidQuorum));
+        assert_eq!(betting.assign_reporters(match_id, vec![accounts.bob, accounts.charlie], 2), Ok(()));
+    }
+
+    #[ink::test]
+    fn challenge_period_delays_settlement() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(5, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::ChallengePeriodActive));
+        // Advance 5 blocks
+        for _ in 0..5 {
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        }
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+    }
+
+    #[ink::test]
+    fn dispute_result_upheld() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+
+        // charlie challenges the result
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Disputed);
+        assert_eq!(betting.get_challenge(match_id).unwrap().challenger, accounts.charlie);
+
+        // alice rules team2 won, charlie gets the bond back
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team2Victory.into()), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie), Ok(90000000000000));
+        assert_eq!(betting.get_challenge(match_id), None);
+
+        // the ruling is final, the match can be settled right away
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie), Ok(140000000000000));
+    }
+
+    #[ink::test]
+    fn set_result_twice() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team2Victory.into()), Err(Error::ResultAlreadySet));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team2Victory.into()), Ok(()));
+
+        // the ruling is final
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Err(Error::ResultAlreadySet));
+        assert_eq!(betting.match_result(match_id), Some(MatchResult::Team2Victory.into()));
+    }
+
+    #[ink::test]
+    fn dispute_result_rejected_slashes_bond() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        assert_eq!(betting.treasury(), 1000000000000);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie), Ok(89000000000000));
+    }
+
+    #[ink::test]
+    fn dispute_result_errors() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+
+        // django did not bet
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Err(Error::BetDoesNotExist));
+        // not enough bond
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1);
+        assert_eq!(betting.dispute_result(match_id), Err(Error::NotEnoughBond));
+        // Advance 10 blocks
+        for _ in 0..10 {
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        }
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Err(Error::ChallengePeriodOver));
+    }
+
+    #[ink::test]
+    fn claim_winnings_during_dispute() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Ok(()));
+
+        // bob can not claim until the dispute is resolved and the match settled
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::MatchDisputed));
+        assert!(!betting.get_bet(match_id, 0).unwrap().claimed);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.resolve_dispute(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(102500000000000));
+    }
+
+    #[ink::test]
+    fn transfer_ownership_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.owner(), Some(accounts.alice));
+
+        assert_eq!(betting.transfer_ownership(accounts.bob), Ok(()));
+        assert_eq!(betting.pending_owner(), Some(accounts.bob));
+        // alice is the owner until bob accepts
+        assert_eq!(betting.owner(), Some(accounts.alice));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.accept_ownership(), Err(Error::NotPendingOwner));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.accept_ownership(), Ok(()));
+        assert_eq!(betting.owner(), Some(accounts.bob));
+        assert_eq!(betting.pending_owner(), None);
+        assert!(betting.has_role(Role::Admin, accounts.bob));
+        assert!(!betting.has_role(Role::Admin, accounts.alice));
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(1, emitted_events.len());
+
+        // alice can not manage the Smart Contract anymore
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.transfer_ownership(accounts.alice), Err(Error::NotOwner));
+        assert_eq!(betting.grant_role(Role::Admin, accounts.alice), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn transfer_ownership_bad_origin() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.transfer_ownership(accounts.bob), Err(Error::NotOwner));
+        assert_eq!(betting.accept_ownership(), Err(Error::NotPendingOwner));
+        assert_eq!(betting.renounce_ownership(), Err(Error::NotOwner));
+        assert_eq!(betting.owner(), Some(accounts.alice));
+    }
+
+    #[ink::test]
+    fn renounce_ownership_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.grant_role(Role::Admin, accounts.bob), Ok(()));
+        assert_eq!(betting.transfer_ownership(accounts.charlie), Ok(()));
+
+        assert_eq!(betting.renounce_ownership(), Ok(()));
+        assert_eq!(betting.owner(), None);
+        // the pending transfer is discarded
+        assert_eq!(betting.pending_owner(), None);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.accept_ownership(), Err(Error::NotPendingOwner));
+        // the admins keep their role
+        assert!(!betting.has_role(Role::Admin, accounts.alice));
+        assert!(betting.has_role(Role::Admin, accounts.bob));
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // role granted + ownership transferred
+        assert_eq!(2, emitted_events.len());
+    }
+
+    #[ink::test]
+    fn slashed_deposit_without_owner() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.grant_role(Role::Admin, accounts.bob), Ok(()));
+        assert_eq!(betting.renounce_ownership(), Ok(()));
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+
+        // the slashed deposit can still be withdrawn by the admins
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Invalid), Ok(()));
+        assert_eq!(betting.treasury(), 1000000000000);
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.frank, 0);
+        assert_eq!(betting.withdraw_fees(accounts.frank, 1000000000000), Ok(()));
+        assert_eq!(betting.treasury(), 0);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank), Ok(1000000000000));
+    }
+
+    #[ink::test]
+    fn pause_stops_the_matches() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.pause(), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.pause(), Ok(()));
+        assert!(betting.paused());
+
+        place_bet_fails(&mut betting, accounts.bob, match_id, Error::Paused);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team3".as_bytes().to_vec(), "team4".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
+            Err(Error::Paused)
+        );
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Err(Error::Paused));
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::Paused));
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::Paused));
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Err(Error::Paused));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.unpause(), Ok(()));
+        assert!(!betting.paused());
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000, MatchResult::Team1Victory.into());
+
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        // 1 match created + paused + unpaused + 1 bet
+        assert_eq!(4, emitted_events.len());
+    }
+
+    #[ink::test]
+    fn dispute_result_while_paused() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_challenge_period(10, 1000000000000), Ok(()));
+        let match_id = create_finished_match(&mut betting);
+        assert_eq!(betting.pause(), Ok(()));
+        assert_eq!(betting.freeze_match(match_id), Ok(()));
+
+        // the challenge period keeps running, so bettors can still dispute
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.dispute_result(match_id), Ok(()));
+
+        for _ in 0..10 {
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        }
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.unpause(), Ok(()));
+        assert_eq!(betting.unfreeze_match(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Err(Error::MatchDisputed));
+    }
+
+    #[ink::test]
+    fn freeze_match_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+        let other_match_id = create_match(&mut betting, accounts.django, "team3", "team4", 10, 10, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.freeze_match(match_id), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.freeze_match(match_id), Ok(()));
+        assert!(betting.get_match(match_id).unwrap().frozen);
+
+        // only the frozen match stops
+        place_bet_fails(&mut betting, accounts.bob, match_id, Error::MatchFrozen);
+        place_bet(&mut betting, accounts.bob, other_match_id, 10000000000, MatchResult::Team1Victory.into());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Err(Error::MatchFrozen));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.unfreeze_match(match_id), Ok(()));
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000, MatchResult::Team1Victory.into());
+    }
+
+    #[ink::test]
+    fn admin_cancels_frozen_match() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.freeze_match(match_id), Ok(()));
+        assert_eq!(betting.pause(), Ok(()));
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Invalid), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().status, MatchStatus::Cancelled);
+    }
+
+    /// Match of the version 1 layout between `team1` and `team2`, starting at block 1, with `bets`.
+    fn v1_match(creator: AccountId, team1: &str, status: MatchStatus, bets: &[BetV1]) -> MatchV1 {
+        let mut totals = vec![0; 3];
+        for bet in bets {
+            totals[bet.result as usize] += bet.amount;
+        }
+        MatchV1 {
+            creator,
+            start: 1,
+            length: 1,
+            team1: team1.as_bytes().to_vec(),
+            team2: "team2".as_bytes().to_vec(),
+            result: None,
+            challenge_end: 0,
+            bets_count: bets.len() as u32,
+            totals,
+            deposit: 1000000000000,
+            status,
+            frozen: false,
+            pot: 0,
+            refunds: status == MatchStatus::Cancelled,
+            claimed_stake: 0,
+            paid_out: 0,
+        }
+    }
+
+    #[ink::test]
+    fn migrate_v1_matches_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.version(), 2);
+        let bets = [
+            BetV1 { bettor: accounts.bob, amount: 10000000000000, result: MatchResult::Team1Victory, claimed: false },
+            BetV1 { bettor: accounts.charlie, amount: 10000000000000, result: MatchResult::Team2Victory, claimed: false },
+            BetV1 { bettor: accounts.eve, amount: 30000000000000, result: MatchResult::Team1Victory, claimed: false },
+        ];
+        let match_id = betting.insert_v1_match(&v1_match(accounts.django, "team1", MatchStatus::Open, &bets), &bets);
+        assert_eq!(betting.version(), 1);
+
+        assert_eq!(betting.migrate(10), Ok(()));
+        assert_eq!(betting.version(), 2);
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(1, emitted_events.len());
+
+        let migrated_match = betting.get_match(match_id).unwrap();
+        assert_eq!(migrated_match.market, Market::MatchResult);
+        assert_eq!(migrated_match.bets_count, 3);
+        assert_eq!(migrated_match.totals, vec![40000000000000, 10000000000000, 0]);
+        assert_eq!(betting.get_bet(match_id, 0), Some(Bet {
+            bettor: accounts.bob,
+            amount: 10000000000000,
+            result: MatchResult::Team1Victory.into(),
+            odds: None,
+            claimed: false,
+        }));
+        // the indexes that did not exist in the version 1 are filled
+        assert_eq!(betting.bets_of(match_id, accounts.eve), vec![2]);
+        assert_eq!(betting.positions_of(accounts.eve, 0, 10).len(), 1);
+        assert_eq!(betting.matches_by_creator(accounts.django, 0, 10), vec![match_id]);
+        assert_eq!(betting.list_matches(MatchPhase::Open, 0, 10), (vec![match_id], None));
+        // the match can not be created twice
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
+            Err(Error::MatchAlreadyExists)
+        );
+
+        // the migrated match works as any other
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        // the stakes of the version 1 were paid before the migration, eve gets 30/40 of the pot of 50
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(137500000000000));
+    }
+
+    #[ink::test]
+    fn migrate_in_batches() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let first_match = betting.insert_v1_match(&v1_match(accounts.django, "team1", MatchStatus::Open, &[]), &[]);
+        let bets = [BetV1 { bettor: accounts.bob, amount: 2000000000000, result: MatchResult::Draw, claimed: false }];
+        let second_match = betting.insert_v1_match(&v1_match(accounts.eve, "team3", MatchStatus::Cancelled, &bets), &bets);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.migrate(1), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.migrate(1), Ok(()));
+        assert_eq!(betting.version(), 1);
+        assert!(betting.get_match(first_match).is_some());
+        assert_eq!(betting.migrate(1), Ok(()));
+        assert_eq!(betting.version(), 2);
+        assert_eq!(betting.migrate(1), Err(Error::AlreadyMigrated));
+        assert_eq!(betting.list_matches(MatchPhase::Cancelled, 0, 10), (vec![second_match], None));
+
+        // the stake of the cancelled match is refunded
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(second_match), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(102000000000000));
+    }
+
+    #[ink::test]
+    fn upgrade_bad_origin() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+
+        assert_eq!(betting.version(), 2);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.upgrade([1u8; 32].into()), Err(Error::NotAdmin));
+    }
+
+    #[ink::test]
+    fn constructor_fee_too_high() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(Betting::new(1001), Err(Error::FeeTooHigh)));
+    }
+
+    #[ink::test]
+    fn fee_collected_on_settle() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(500).unwrap();
+        assert_eq!(betting.fee(), 500);
+        let match_id = create_finished_match(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        // 5% of the pot goes to the treasury
+        assert_eq!(betting.treasury(), 2500000000000);
+        assert_eq!(betting.get_match(match_id).unwrap().pot, 47500000000000);
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(7, emitted_events.len());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(101875000000000));
+    }
+
+    #[ink::test]
+    fn no_fee_without_winners() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(500).unwrap();
+        let match_id = create_match_without_winners(&mut betting);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.treasury(), 0);
+    }
+
+    #[ink::test]
+    fn set_fee_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.set_fee(1001), Err(Error::FeeTooHigh));
+        assert_eq!(betting.set_fee(1000), Ok(()));
+        assert_eq!(betting.fee(), 1000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_fee(0), Err(Error::NotAdmin));
+    }
+
+    #[ink::test]
+    fn withdraw_fees_works() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(1000).unwrap();
+        let match_id = create_finished_match(&mut betting);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.treasury(), 5000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.withdraw_fees(accounts.bob, 1000000000000), Err(Error::NotAdmin));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.withdraw_fees(accounts.frank, 6000000000000), Err(Error::InsufficientTreasury));
+
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.frank, 0);
+        assert_eq!(betting.withdraw_fees(accounts.frank, 3000000000000), Ok(()));
+        assert_eq!(betting.treasury(), 2000000000000);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank), Ok(3000000000000));
+    }
+
+    #[ink::test]
+    fn creator_commission_works() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(500).unwrap();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        let match_id = betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 200, BetLimits::default(), MatchMetadata::default()).unwrap();
+        assert_eq!(betting.get_match(match_id).unwrap().commission_bps, 200);
+        assert_eq!(betting.match_view(match_id).unwrap().commission_bps, 200);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 10000000000000, MatchResult::Team2Victory.into());
+        place_bet(&mut betting, accounts.eve, match_id, 30000000000000, MatchResult::Team1Victory.into());
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        // 5% of the pot to the treasury and 2% to the creator
+        assert_eq!(betting.treasury(), 2500000000000);
+        assert_eq!(betting.get_match(match_id).unwrap().pot, 46500000000000);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(101000000000000));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(101625000000000));
+    }
+
+    #[ink::test]
+    fn creator_commission_too_high() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 1001, BetLimits::default(), MatchMetadata::default()),
+            Err(Error::CommissionTooHigh)
+        );
+    }
+
+    fn bet_fails(betting: &mut Betting, who: AccountId, match_id: MatchId, amount: u128, error: Error) {
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(amount);
+        assert_eq!(betting.bet(match_id, MatchResult::Team1Victory.into()), Err(error));
+    }
+
+    #[ink::test]
+    fn bet_stake_limits_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        let limits = BetLimits { min_stake: 1000000000000, max_stake: 20000000000000, max_bets: 1 };
+        let match_id = betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, limits, MatchMetadata::default()).unwrap();
+        assert_eq!(betting.get_match(match_id).unwrap().bet_limits, limits);
+
+        bet_fails(&mut betting, accounts.bob, match_id, 0, Error::BetTooSmall);
+        bet_fails(&mut betting, accounts.bob, match_id, 999999999999, Error::BetTooSmall);
+        bet_fails(&mut betting, accounts.bob, match_id, 20000000000001, Error::BetTooLarge);
+        place_bet(&mut betting, accounts.bob, match_id, 20000000000000, MatchResult::Team1Victory.into());
+
+        // the global maximum applies to every match
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_limits(1000000000000, 0, 5000000000000), Ok(()));
+        bet_fails(&mut betting, accounts.eve, match_id, 10000000000000, Error::BetTooLarge);
+        place_bet(&mut betting, accounts.eve, match_id, 5000000000000, MatchResult::Team2Victory.into());
+    }
+
+    #[ink::test]
+    fn zero_bet_fails() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+        bet_fails(&mut betting, accounts.bob, match_id, 0, Error::BetTooSmall);
+    }
+
+    #[ink::test]
+    fn invalid_stake_limits() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, BetLimits { min_stake: 2, max_stake: 1, max_bets: 1 }, MatchMetadata::default()),
+            Err(Error::InvalidBetLimits)
+        );
+    }
+
+    #[ink::test]
+    fn set_limits_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.min_deposit(), 1000000000000);
+        assert_eq!(betting.min_stake(), 1000000000);
+        assert_eq!(betting.max_stake(), u128::MAX);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.set_limits(0, 0, 0), Err(Error::NotAdmin));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_limits(2000000000000, 50000000000001, 50000000000000), Err(Error::InvalidBetLimits));
+        assert_eq!(betting.min_stake(), 1000000000);
+        assert_eq!(betting.set_limits(2000000000000, 1000000000000, 50000000000000), Ok(()));
+        assert_eq!(betting.min_deposit(), 2000000000000);
+        assert_eq!(betting.min_stake(), 1000000000000);
+        assert_eq!(betting.max_stake(), 50000000000000);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default()),
+            Err(Error::NotEnoughDeposit)
+        );
+    }
+
+    #[ink::test]
+    fn dust_bet_fails() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 10, 10, 1000000000000);
+        bet_fails(&mut betting, accounts.bob, match_id, 999999999, Error::BetTooSmall);
+        place_bet(&mut betting, accounts.bob, match_id, 1000000000, MatchResult::Team1Victory.into());
+    }
+
+    #[ink::test]
+    fn multiple_bets_per_account_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(
+            betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits { max_bets: 0, ..Default::default() }, MatchMetadata::default()),
+            Err(Error::InvalidBetLimits)
+        );
+        let match_id = betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, 0, BetLimits { max_bets: 3, ..Default::default() }, MatchMetadata::default()).unwrap();
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.bob, match_id, 5000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.bob, match_id, 5000000000000, MatchResult::Team2Victory.into());
+        bet_fails(&mut betting, accounts.bob, match_id, 5000000000000, Error::AlreadyBet);
+        place_bet(&mut betting, accounts.eve, match_id, 5000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 15000000000000, MatchResult::Team2Victory.into());
+        assert_eq!(betting.get_match(match_id).unwrap().bets_count, 5);
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        // both winning bets of bob are claimed at once: 15/20 of a pot of 40
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(balance + 30000000000000));
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::AlreadyClaimed));
+        assert!(betting.get_bet(match_id, 1).unwrap().claimed);
+        assert!(!betting.get_bet(match_id, 2).unwrap().claimed);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::NotWinningBet));
+    }
+
+    #[ink::test]
+    fn increase_bet_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 2, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        assert_eq!(betting.bets_of(match_id, accounts.bob), vec![0]);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(5000000000000);
+        assert_eq!(betting.increase_bet(match_id, 0), Ok(()));
+        assert_eq!(betting.get_bet(match_id, 0).unwrap().amount, 15000000000000);
+        assert_eq!(betting.get_match(match_id).unwrap().totals, vec![15000000000000, 0, 0]);
+        let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(3, emitted_events.len());
+
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(0);
+        assert_eq!(betting.increase_bet(match_id, 0), Err(Error::BetTooSmall));
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        assert_eq!(betting.increase_bet(match_id, 1), Err(Error::BetDoesNotExist));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.increase_bet(match_id, 0), Err(Error::NotBettor));
+
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.increase_bet(match_id, 0), Err(Error::MatchHasStarted));
+    }
+
+    #[ink::test]
+    fn change_prediction_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 2, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 5000000000000, MatchResult::Team1Victory.into());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.change_prediction(match_id, 0, MatchResult::Draw.into()), Ok(()));
+        assert_eq!(betting.get_bet(match_id, 0).unwrap().result, MatchResult::Draw.into());
+        assert_eq!(betting.get_match(match_id).unwrap().totals, vec![5000000000000, 0, 10000000000000]);
+        assert_eq!(betting.change_prediction(match_id, 1, MatchResult::Draw.into()), Err(Error::NotBettor));
+
+        // Advance 3 blocks
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        assert_eq!(betting.change_prediction(match_id, 0, MatchResult::Team2Victory.into()), Err(Error::MatchHasStarted));
+    }
+
+    #[ink::test]
+    fn cancel_bet_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 2, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 5000000000000, MatchResult::Team2Victory.into());
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.cancel_bet(match_id, 0), Err(Error::NotBettor));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.cancel_bet(match_id, 0), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(100000000000000));
+        assert_eq!(betting.get_bet(match_id, 0), None);
+        assert_eq!(betting.bets_of(match_id, accounts.bob), vec![]);
+        assert_eq!(betting.get_match(match_id).unwrap().totals, vec![0, 5000000000000, 0]);
+        assert_eq!(betting.cancel_bet(match_id, 0), Err(Error::BetDoesNotExist));
+
+        // the account can bet again
+        place_bet(&mut betting, accounts.bob, match_id, 1000000000000, MatchResult::Draw.into());
+        assert_eq!(betting.bets_of(match_id, accounts.bob), vec![2]);
+    }
+
+    /// Django offers odds of 2, 3 and 4 backed by a deposit of 10, Bob and Eve bet on team1 and Charlie on team2.
+    fn create_fixed_odds_match(betting: &mut Betting) -> MatchId {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        let match_id = betting.create_fixed_odds_match("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, BetLimits::default(), vec![20000, 30000, 40000], MatchMetadata::default()).unwrap();
+        place_bet(betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(betting, accounts.charlie, match_id, 10000000000000, MatchResult::Team2Victory.into());
+        place_bet(betting, accounts.eve, match_id, 5000000000000, MatchResult::Team1Victory.into());
+        match_id
+    }
+
+    #[ink::test]
+    fn create_fixed_odds_match_invalid_odds() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        assert_eq!(
+            betting.create_fixed_odds_match("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, BetLimits::default(), vec![20000, 30000], MatchMetadata::default()),
+            Err(Error::InvalidOdds)
+        );
+        assert_eq!(
+            betting.create_fixed_odds_match("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, Market::MatchResult, BetLimits::default(), vec![20000, 10000, 30000], MatchMetadata::default()),
+            Err(Error::InvalidOdds)
+        );
+    }
+
+    #[ink::test]
+    fn fixed_odds_bet_locks_the_odds() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_fixed_odds_match(&mut betting);
+        assert_eq!(betting.get_bet(match_id, 0).unwrap().odds, Some(20000));
+        assert_eq!(betting.get_match(match_id).unwrap().payouts, vec![30000000000000, 30000000000000, 0]);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.update_odds(match_id, vec![15000, 30000, 50000]), Err(Error::NotMatchCreator));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.update_odds(match_id, vec![15000, 30000]), Err(Error::InvalidOdds));
+        assert_eq!(betting.update_odds(match_id, vec![15000, 30000, 50000]), Ok(()));
+        assert_eq!(betting.get_match(match_id).unwrap().odds, Some(vec![15000, 30000, 50000]));
+        // the bets already placed keep their odds
+        assert_eq!(betting.get_bet(match_id, 0).unwrap().odds, Some(20000));
+
+        // changing the prediction takes the current odds of the new result
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.change_prediction(match_id, 2, MatchResult::Draw.into()), Ok(()));
+        assert_eq!(betting.get_bet(match_id, 2).unwrap().odds, Some(50000));
+        assert_eq!(betting.get_match(match_id).unwrap().payouts, vec![20000000000000, 30000000000000, 25000000000000]);
+
+        let parimutuel_match = create_match(&mut betting, accounts.django, "team3", "team4", 1, 1, 1000000000000);
+        assert_eq!(betting.update_odds(parimutuel_match, vec![15000, 30000, 50000]), Err(Error::NotFixedOdds));
+    }
+
+    #[ink::test]
+    fn fixed_odds_bet_needs_collateral() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_fixed_odds_match(&mut betting);
+        ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.frank, 100000000000000);
+
+        // a payout of 80 on a draw is not covered by the deposit and the stakes
+        bet_fails(&mut betting, accounts.frank, match_id, 20000000000000, Error::InsufficientCollateral);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        assert_eq!(betting.bet(match_id, MatchResult::Draw.into()), Ok(()));
+        // nor a larger payout for team2 if the stake on team1 is withdrawn
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        assert_eq!(betting.increase_bet(match_id, 1), Err(Error::InsufficientCollateral));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+        assert_eq!(betting.cancel_bet(match_id, 3), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.cancel_bet(match_id, 0), Err(Error::InsufficientCollateral));
+    }
+
+    #[ink::test]
+    fn fixed_odds_settle_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_fixed_odds_match(&mut betting);
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+
+        // the creator pays 30 to the winners from the 25 staked and its deposit
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(95000000000000));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(110000000000000));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(105000000000000));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert_eq!(betting.claim_winnings(match_id), Err(Error::NotWinningBet));
+    }
+
+    #[ink::test]
+    fn fixed_odds_fee_on_the_margin() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(1000).unwrap();
+        let match_id = create_fixed_odds_match(&mut betting);
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Draw.into()), Ok(()));
+
+        // nobody wins, the creator keeps the 25 staked minus a fee of 10%
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.treasury(), 2500000000000);
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django), Ok(122500000000000));
+    }
+
+    #[ink::test]
+    fn implied_odds_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.implied_odds(0), Err(Error::MatchDoesNotExist));
+        let match_id = create_match(&mut betting, accounts.django, "team3", "team4", 1, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 10000000000000, MatchResult::Team2Victory.into());
+        place_bet(&mut betting, accounts.eve, match_id, 30000000000000, MatchResult::Team1Victory.into());
+
+        assert_eq!(betting.pool_totals(match_id), Some(vec![40000000000000, 10000000000000, 0]));
+        assert_eq!(betting.implied_odds(match_id), Ok(vec![12500, 50000, 0]));
+
+        let fixed_odds_match = create_fixed_odds_match(&mut betting);
+        assert_eq!(betting.implied_odds(fixed_odds_match), Ok(vec![20000, 30000, 40000]));
+        assert_eq!(betting.quote_payout(fixed_odds_match, MatchResult::Draw.into(), 1000000000000), Ok(4000000000000));
+    }
+
+    #[ink::test]
+    fn quote_payout_matches_settlement() {
+        let accounts = set_accounts();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut betting = Betting::new(500).unwrap();
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 10000000000000, MatchResult::Team2Victory.into());
+
+        // 30/40 of a pot of 50 minus a fee of 5%
+        let quote = betting.quote_payout(match_id, MatchResult::Team1Victory.into(), 30000000000000).unwrap();
+        assert_eq!(quote, 35625000000000);
+        place_bet(&mut betting, accounts.eve, match_id, 30000000000000, MatchResult::Team1Victory.into());
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(70000000000000 + quote));
+    }
+
+    fn create_market(betting: &mut Betting, who: AccountId, market: Market) -> Result<MatchId, Error> {
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(who);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 1, 1, market, 0, BetLimits::default(), MatchMetadata::default())
+    }
+
+    #[ink::test]
+    fn outcomes_market_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let runners = ["horse1", "horse2", "horse3", "horse4"].iter()
+            .map(|name| TeamName::try_from(name.as_bytes().to_vec()).unwrap())
+            .collect();
+        let match_id = create_market(&mut betting, accounts.django, Market::Outcomes(runners)).unwrap();
+        assert_eq!(betting.pool_totals(match_id), Some(vec![0, 0, 0, 0]));
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, 3);
+        place_bet(&mut betting, accounts.charlie, match_id, 10000000000000, 0);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        assert_eq!(betting.bet(match_id, 4), Err(Error::InvalidOutcome));
+
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, 4), Err(Error::InvalidOutcome));
+        assert_eq!(betting.set_result(match_id, 3), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(110000000000000));
+    }
+
+    #[ink::test]
+    fn invalid_market() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let horse = TeamName::try_from("horse1".as_bytes().to_vec()).unwrap();
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Outcomes(vec![horse.clone()])), Err(Error::InvalidMarket));
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Outcomes(vec![horse.clone(), horse])), Err(Error::InvalidMarket));
+        let runners = (0..33).map(|i| TeamName::try_from(i.to_string().into_bytes()).unwrap()).collect();
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Outcomes(runners)), Err(Error::InvalidMarket));
+        // a score landing on a whole line would be a push
+        assert_eq!(create_market(&mut betting, accounts.django, Market::OverUnder { line: 30 }), Err(Error::InvalidMarket));
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Handicap { line: 0 }), Err(Error::InvalidMarket));
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Handicap { line: -12 }), Err(Error::InvalidMarket));
+    }
+
+    #[ink::test]
+    fn over_under_and_handicap_markets() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        // several markets can be offered on the same match
+        let match_result = create_market(&mut betting, accounts.django, Market::MatchResult).unwrap();
+        let over_under = create_market(&mut betting, accounts.django, Market::OverUnder { line: 25 }).unwrap();
+        let handicap = create_market(&mut betting, accounts.django, Market::Handicap { line: -15 }).unwrap();
+        assert_eq!(create_market(&mut betting, accounts.django, Market::Handicap { line: -15 }), Err(Error::MatchAlreadyExists));
+        assert_eq!(betting.pool_totals(match_result), Some(vec![0, 0, 0]));
+        assert_eq!(betting.pool_totals(over_under), Some(vec![0, 0]));
+        assert_eq!(betting.get_match(handicap).unwrap().market, Market::Handicap { line: -15 });
+
+        place_bet(&mut betting, accounts.bob, over_under, 10000000000000, 1);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.change_prediction(over_under, 0, MatchResult::Draw.into()), Err(Error::InvalidOutcome));
+        assert_eq!(betting.quote_payout(handicap, 2, 1000000000000), Err(Error::InvalidOutcome));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(10000000000000);
+        assert_eq!(
+            betting.create_fixed_odds_match("team3".as_bytes().to_vec(), "team4".as_bytes().to_vec(), 1, 1, Market::OverUnder { line: 25 }, BetLimits::default(), vec![20000, 20000, 20000], MatchMetadata::default()),
+            Err(Error::InvalidOdds)
+        );
+        assert!(betting.create_fixed_odds_match("team3".as_bytes().to_vec(), "team4".as_bytes().to_vec(), 1, 1, Market::OverUnder { line: 25 }, BetLimits::default(), vec![19000, 19000], MatchMetadata::default()).is_ok());
+    }
+
+    #[ink::test]
+    fn create_match_invalid_team_names() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        let create = |betting: &mut Betting, team1: Vec<u8>, team2: Vec<u8>| {
+            betting.create_match_to_bet(team1, team2, 1, 1, Market::MatchResult, 0, BetLimits::default(), MatchMetadata::default())
+        };
+        assert_eq!(create(&mut betting, vec![], "team2".as_bytes().to_vec()), Err(Error::EmptyTeamName));
+        assert_eq!(create(&mut betting, "team1".as_bytes().to_vec(), vec![b'a'; MAX_TEAM_NAME_LEN + 1]), Err(Error::TeamNameTooLong));
+        assert_eq!(create(&mut betting, vec![0xff, 0xfe], "team2".as_bytes().to_vec()), Err(Error::InvalidTeamName));
+        assert_eq!(create(&mut betting, "team1".as_bytes().to_vec(), "team1".as_bytes().to_vec()), Err(Error::SameTeams));
+        assert_eq!(betting.matches_by_creator(accounts.django, 0, 10).len(), 0);
+
+        assert_eq!(create(&mut betting, "Atlético".as_bytes().to_vec(), vec![b'a'; MAX_TEAM_NAME_LEN]), Ok(0));
+    }
+
+    #[ink::test]
+    fn team_name_validation() {
+        assert_eq!(TeamName::try_from(vec![]), Err(Error::EmptyTeamName));
+        assert_eq!(TeamName::try_from(vec![0xc3]), Err(Error::InvalidTeamName));
+        assert_eq!(TeamName::try_from("team1".as_bytes().to_vec()).unwrap().as_bytes(), "team1".as_bytes());
+    }
+
+    fn create_match_with_metadata(betting: &mut Betting, metadata: MatchMetadata) -> Result<MatchId, Error> {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000000000000);
+        betting.create_match_to_bet("team1".as_bytes().to_vec(), "team2".as_bytes().to_vec(), 10, 10, Market::MatchResult, 0, BetLimits::default(), metadata)
+    }
+
+    #[ink::test]
+    fn match_metadata_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.get_metadata(0), None);
+        let metadata = MatchMetadata {
+            sport: Some(Sport::Football),
+            competition: Some(TeamName::try_from("LaLiga".as_bytes().to_vec()).unwrap()),
+            season: Some(2024),
+            fixture_id: Some(42),
+            metadata_hash: Some([1u8; 32].into()),
+        };
+        let match_id = create_match_with_metadata(&mut betting, metadata.clone()).unwrap();
+        assert_eq!(betting.get_metadata(match_id), Some(metadata));
+
+        let without_metadata = create_match(&mut betting, accounts.django, "team3", "team4", 10, 10, 1000000000000);
+        assert_eq!(betting.get_metadata(without_metadata), Some(MatchMetadata::default()));
+    }
+
+    #[ink::test]
+    fn match_metadata_invalid_competition() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let empty_name: TeamName = scale::Decode::decode(&mut &scale::Encode::encode(&Vec::<u8>::new())[..]).unwrap();
+        let metadata = MatchMetadata {
+            competition: Some(empty_name),
+            ..Default::default()
+        };
+        assert_eq!(create_match_with_metadata(&mut betting, metadata), Err(Error::EmptyTeamName));
+        assert_eq!(betting.matches_by_creator(accounts.django, 0, 10).len(), 0);
+    }
+
+    #[ink::test]
+    fn fixture_id_in_match_hash() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let first_fixture = MatchMetadata { fixture_id: Some(42), ..Default::default() };
+        let second_fixture = MatchMetadata { fixture_id: Some(43), ..Default::default() };
+        let match_id = create_match_with_metadata(&mut betting, first_fixture.clone()).unwrap();
+        // same teams and time but another fixture
+        assert!(create_match_with_metadata(&mut betting, second_fixture).is_ok());
+        assert_eq!(create_match_with_metadata(&mut betting, first_fixture.clone()), Err(Error::MatchAlreadyExists));
+
+        // the fixture is free again once the match is cancelled
+        assert_eq!(betting.cancel_match(match_id, CancelReason::Postponed), Ok(()));
+        assert!(create_match_with_metadata(&mut betting, first_fixture).is_ok());
+    }
+
+    #[ink::test]
+    fn list_matches_by_phase() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let not_started = create_match(&mut betting, accounts.django, "a", "b", 10, 10, 1000000000000);
+        let finished = create_finished_match(&mut betting);
+        // now at block 3
+        let starting = create_match(&mut betting, accounts.django, "c", "d", 3, 5, 1000000000000);
+        let in_play = create_match(&mut betting, accounts.django, "e", "f", 1, 5, 1000000000000);
+        let cancelled = create_match(&mut betting, accounts.django, "g", "h", 10, 10, 1000000000000);
+        assert_eq!(betting.cancel_match(cancelled, CancelReason::Postponed), Ok(()));
+
+        assert_eq!(betting.list_matches(MatchPhase::Open, 0, 10), (vec![not_started, starting], None));
+        assert_eq!(betting.list_matches(MatchPhase::InPlay, 0, 10), (vec![in_play], None));
+        assert_eq!(betting.list_matches(MatchPhase::AwaitingResult, 0, 10), (vec![finished], None));
+        assert_eq!(betting.list_matches(MatchPhase::Cancelled, 0, 10), (vec![cancelled], None));
+        assert_eq!(betting.list_matches(MatchPhase::Settled, 0, 10), (vec![], None));
+
+        assert_eq!(betting.distribute_winnings(finished), Ok(()));
+        assert_eq!(betting.list_matches(MatchPhase::Settled, 0, 10), (vec![finished], None));
+        assert_eq!(betting.list_matches(MatchPhase::AwaitingResult, 0, 10), (vec![], None));
+        // the last active match takes the position of the settled one
+        assert_eq!(betting.list_matches(MatchPhase::Open, 0, 1), (vec![not_started], Some(1)));
+        assert_eq!(betting.list_matches(MatchPhase::Open, 1, 2), (vec![starting], None));
+        assert_eq!(betting.list_matches(MatchPhase::Open, 3, 2), (vec![], None));
+        // a page can be empty before the end of the index
+        assert_eq!(betting.list_matches(MatchPhase::InPlay, 0, 1), (vec![], Some(1)));
+    }
+
+    #[ink::test]
+    fn matches_by_creator_paginated() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        for team in ["a", "b", "c", "d", "e"] {
+            create_match(&mut betting, accounts.django, team, "z", 10, 10, 1000000000000);
+        }
+        create_match(&mut betting, accounts.eve, "f", "z", 10, 10, 1000000000000);
+
+        assert_eq!(betting.matches_by_creator(accounts.django, 0, 2), vec![0, 1]);
+        assert_eq!(betting.matches_by_creator(accounts.django, 2, 2), vec![2, 3]);
+        assert_eq!(betting.matches_by_creator(accounts.django, 4, 2), vec![4]);
+        assert_eq!(betting.matches_by_creator(accounts.django, 10, 2), vec![]);
+        assert_eq!(betting.matches_by_creator(accounts.eve, 0, 10), vec![5]);
+        assert_eq!(betting.matches_by_creator(accounts.django, 0, 10), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[ink::test]
+    fn positions_of_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_finished_match(&mut betting);
+        let cancelled = create_match(&mut betting, accounts.django, "team3", "team4", 10, 10, 1000000000000);
+        place_bet(&mut betting, accounts.eve, cancelled, 2000000000000, MatchResult::Draw.into());
+
+        let pending = Position {
+            match_id,
+            bet_id: 0,
+            amount: 10000000000000,
+            result: MatchResult::Team1Victory.into(),
+            status: PositionStatus::Pending,
+            claimable: 0,
+        };
+        assert_eq!(betting.positions_of(accounts.bob, 0, 10), vec![pending]);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.cancel_match(cancelled, CancelReason::Postponed), Ok(()));
+
+        // pot of 50, bob gets 10/40 of it and eve 30/40
+        let positions = betting.positions_of(accounts.eve, 0, 10);
+        assert_eq!(positions.len(), 2);
+        assert_eq!((positions[0].status, positions[0].claimable), (PositionStatus::Won, 37500000000000));
+        assert_eq!((positions[1].status, positions[1].claimable), (PositionStatus::Refundable, 2000000000000));
+        let positions = betting.positions_of(accounts.charlie, 0, 10);
+        assert_eq!((positions[0].status, positions[0].claimable), (PositionStatus::Lost, 0));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+        assert_eq!(betting.claim_winnings(match_id), Ok(()));
+        assert_eq!(betting.claim_winnings(cancelled), Ok(()));
+        let positions = betting.positions_of(accounts.eve, 0, 10);
+        assert_eq!((positions[0].status, positions[0].claimable), (PositionStatus::Claimed, 0));
+        assert_eq!((positions[1].status, positions[1].claimable), (PositionStatus::Claimed, 0));
+    }
+
+    #[ink::test]
+    fn positions_of_fixed_odds() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        let match_id = create_fixed_odds_match(&mut betting);
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+
+        // eve bet 5 at odds of 2
+        let positions = betting.positions_of(accounts.eve, 0, 10);
+        assert_eq!((positions[0].status, positions[0].claimable), (PositionStatus::Won, 10000000000000));
+    }
+
+    #[ink::test]
+    fn positions_of_paginated() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        for team in ["a", "b", "c", "d"] {
+            let match_id = create_match(&mut betting, accounts.django, team, "z", 10, 10, 1000000000000);
+            place_bet(&mut betting, accounts.bob, match_id, 1000000000000, MatchResult::Draw.into());
+        }
+        let match_ids = |positions: Vec<Position>| positions.iter().map(|position| position.match_id).collect::<Vec<_>>();
+        assert_eq!(match_ids(betting.positions_of(accounts.bob, 0, 3)), vec![0, 1, 2]);
+        assert_eq!(match_ids(betting.positions_of(accounts.bob, 3, 3)), vec![3]);
+        assert_eq!(match_ids(betting.positions_of(accounts.bob, 10, 3)), vec![]);
+        assert_eq!(betting.positions_of(accounts.charlie, 0, 10), vec![]);
+
+        // the last bet takes the position of the cancelled one
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(betting.cancel_bet(1, 0), Ok(()));
+        assert_eq!(match_ids(betting.positions_of(accounts.bob, 0, 10)), vec![0, 3, 2]);
+        assert_eq!(betting.cancel_bet(2, 0), Ok(()));
+        assert_eq!(match_ids(betting.positions_of(accounts.bob, 0, 10)), vec![0, 3]);
+    }
+
+    #[ink::test]
+    fn match_view_works() {
+        let accounts = set_accounts();
+        let mut betting = create_contract(accounts.alice);
+        assert_eq!(betting.match_view(0), None);
+        assert_eq!(betting.match_status(0), None);
+        assert_eq!(betting.match_result(0), None);
+
+        let match_id = create_match(&mut betting, accounts.django, "team1", "team2", 1, 1, 1000000000000);
+        place_bet(&mut betting, accounts.bob, match_id, 10000000000000, MatchResult::Team1Victory.into());
+        place_bet(&mut betting, accounts.charlie, match_id, 5000000000000, MatchResult::Draw.into());
+        assert_eq!(betting.match_result(match_id), None);
+
+        let view = MatchView {
+            version: MATCH_VIEW_VERSION,
+            match_id,
+            creator: accounts.django,
+            team1: TeamName::try_from("team1".as_bytes().to_vec()).unwrap(),
+            team2: TeamName::try_from("team2".as_bytes().to_vec()).unwrap(),
+            market: Market::MatchResult,
+            commission_bps: 0,
+            bet_limits: BetLimits::default(),
+            start: 1,
+            end: 2,
+            challenge_end: 0,
+            status: MatchStatus::Open,
+            phase: MatchPhase::Open,
+            result: None,
+            totals: vec![10000000000000, 0, 5000000000000],
+            bets_count: 2,
+            odds: None,
+            deposit: 1000000000000,
+            pot: 0,
+        };
+        assert_eq!(betting.match_view(match_id), Some(view));
+
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert_eq!(betting.set_result(match_id, MatchResult::Team1Victory.into()), Ok(()));
+        assert_eq!(betting.match_result(match_id), Some(MatchResult::Team1Victory.into()));
+        assert_eq!(betting.match_view(match_id).unwrap().phase, MatchPhase::AwaitingResult);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        assert_eq!(betting.distribute_winnings(match_id), Ok(()));
+        assert_eq!(betting.match_status(match_id), Some(MatchStatus::Settled));
+        let view = betting.match_view(match_id).unwrap();
+        assert_eq!((view.phase, view.pot), (MatchPhase::Settled, 15000000000000));
+        // the fields of the stored match are readable too
+        let betting_match = betting.get_match(match_id).unwrap();
+        assert_eq!((betting_match.creator, betting_match.start, betting_match.length), (accounts.django, 1, 1));
     }
-}
\ No newline at end of file
+}